@@ -1,10 +1,15 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use semver::Version;
-use view_library::{options::Options, MainWindow};
+use view_library::{
+    convert::{self, ConvertOptions},
+    loader,
+    options::Options,
+    MainWindow,
+};
 
 lazy_static::lazy_static! {
     static ref VERSION: Version = Version::parse( env!("CARGO_PKG_VERSION")).unwrap();
@@ -23,20 +28,185 @@ lazy_static::lazy_static! {
 }
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub struct Cli {
     path: Option<PathBuf>,
 
     #[clap(long, default_value_t = false, help = "Enable auto-scrolling")]
     auto: bool,
+
+    #[clap(long, default_value_t = false, help = "Start a slideshow of the folder")]
+    slideshow: bool,
+
+    #[clap(long, requires = "slideshow", help = "Seconds each file is shown in the slideshow")]
+    delay: Option<u64>,
+
+    #[clap(long, default_value_t = false, requires = "slideshow", help = "Play the slideshow in random order")]
+    shuffle: bool,
+
+    #[clap(
+        long = "loop",
+        default_value_t = false,
+        requires = "slideshow",
+        help = "Restart the slideshow after the last file"
+    )]
+    repeat: bool,
+
+    #[clap(long, default_value_t = false, requires = "slideshow", help = "Include all sub folders in the slideshow")]
+    recursive: bool,
+
+    #[clap(
+        long,
+        default_value_t = false,
+        requires = "slideshow",
+        help = "Advance by time only, don't wait for scrolling files to reach the end"
+    )]
+    no_wait: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    #[command(about = "Render a file to a PNG image without opening a window")]
+    Export {
+        input: PathBuf,
+        #[clap(help = "Output file, defaults to the input file with a .png extension")]
+        output: Option<PathBuf>,
+    },
+    #[command(about = "Convert files to another format, folders convert all files they contain")]
+    Convert {
+        #[clap(required = true)]
+        inputs: Vec<PathBuf>,
+        #[clap(long, short, help = "Extension of the target format, for example ans, xb or bin")]
+        format: String,
+        #[clap(long, short, help = "Output file for a single input, otherwise the output folder")]
+        output: Option<PathBuf>,
+        #[clap(long, default_value_t = false, help = "Don't write a SAUCE record")]
+        no_sauce: bool,
+        #[clap(long, default_value_t = false, help = "Don't compress the output")]
+        no_compress: bool,
+        #[clap(long, help = "Wrap output lines after this many characters")]
+        line_length: Option<usize>,
+        #[clap(long, default_value_t = false, help = "Replace existing output files")]
+        overwrite: bool,
+    },
+}
+
+fn export_png(input: &Path, output: Option<PathBuf>) -> i32 {
+    let output = output.unwrap_or_else(|| input.with_extension("png"));
+    match loader::render_file(input) {
+        Ok(img) => {
+            if let Err(err) = img.save_with_format(&output, image::ImageFormat::Png) {
+                eprintln!("Error writing {}: {err}", output.display());
+                return 1;
+            }
+            0
+        }
+        Err(err) => {
+            eprintln!("Error rendering {}: {err}", input.display());
+            1
+        }
+    }
+}
+
+fn convert_files(inputs: &[PathBuf], extension: &str, output: Option<PathBuf>, options: &ConvertOptions, overwrite: bool) -> i32 {
+    let mut files = Vec::new();
+    for input in inputs {
+        if input.is_dir() {
+            match convert::get_convertible_files(input) {
+                Ok(dir_files) => files.extend(dir_files),
+                Err(err) => {
+                    eprintln!("Error reading {}: {err}", input.display());
+                    return 1;
+                }
+            }
+        } else {
+            files.push(input.clone());
+        }
+    }
+
+    // a single file can be converted to an output file, batches need an output folder
+    let single_output = output.as_ref().filter(|output| files.len() == 1 && inputs[0].is_file() && !output.is_dir());
+    if let Some(output_dir) = output.as_ref().filter(|_| single_output.is_none()) {
+        if let Err(err) = std::fs::create_dir_all(output_dir) {
+            eprintln!("Error creating {}: {err}", output_dir.display());
+            return 1;
+        }
+    }
+
+    let jobs: Vec<(PathBuf, PathBuf)> = files
+        .into_iter()
+        .map(|file| {
+            let output_file = match single_output {
+                Some(output) => output.clone(),
+                None => convert::get_output_path(&file, output.as_deref(), extension),
+            };
+            (file, output_file)
+        })
+        .collect();
+    // all outputs are checked first, so conflicts are reported before anything is written
+    let conflicts = convert::get_output_conflicts(&jobs, overwrite);
+
+    let mut result = 0;
+    for ((file, output_file), conflict) in jobs.iter().zip(conflicts) {
+        if let Some(conflict) = conflict {
+            eprintln!("Skipping {}: {conflict}", file.display());
+            result = 1;
+            continue;
+        }
+        match convert::convert_file(file, output_file, extension, options) {
+            Ok(()) => println!("{} -> {}", file.display(), output_file.display()),
+            Err(err) => {
+                eprintln!("Error converting {}: {err}", file.display());
+                result = 1;
+            }
+        }
+    }
+    result
 }
 
 fn main() {
     let args = Cli::parse();
+    if let Some(command) = args.command {
+        match command {
+            Commands::Export { input, output } => std::process::exit(export_png(&input, output)),
+            Commands::Convert {
+                inputs,
+                format,
+                output,
+                no_sauce,
+                no_compress,
+                line_length,
+                overwrite,
+            } => {
+                let options = ConvertOptions {
+                    save_sauce: !no_sauce,
+                    compress: !no_compress,
+                    line_length,
+                };
+                std::process::exit(convert_files(&inputs, &format, output, &options, overwrite))
+            }
+        }
+    }
+
     let mut options = Options::load_options();
     if args.auto {
         options.auto_scroll_enabled = true;
     }
+    // the slideshow arguments only apply to this run, they must not end up in the stored options
+    let slideshow_options = args.slideshow.then(|| {
+        let mut slideshow = options.slideshow.clone();
+        if let Some(delay) = args.delay {
+            slideshow.delay = delay;
+        }
+        slideshow.shuffle |= args.shuffle;
+        slideshow.repeat |= args.repeat;
+        slideshow.recursive |= args.recursive;
+        slideshow.wait_for_end &= !args.no_wait;
+        slideshow
+    });
 
     let native_options = eframe::NativeOptions {
         //initial_window_size: Some(egui::vec2(1284. + 8., 839.)),
@@ -49,7 +219,7 @@ fn main() {
     eframe::run_native(
         &DEFAULT_TITLE,
         native_options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             let gl = cc.gl.as_ref().expect("You need to run eframe with the glow backend");
             egui_extras::install_image_loaders(&cc.egui_ctx);
 
@@ -60,6 +230,9 @@ fn main() {
             }
             let cmd = fd.file_view.refresh();
             fd.handle_command(cmd);
+            if slideshow_options.is_some() {
+                fd.start_slideshow(slideshow_options);
+            }
             Box::new(fd)
         }),
     )
@@ -2,6 +2,11 @@ heading-title=Titel
 heading-author=Autor
 heading-group=Gruppe
 heading-screen-mode=Flags
+heading-name=Name
+heading-size=Größe
+heading-modified=Geändert
+heading-date=Datum
+heading-dimensions=Größe (Zeichen)
 
 menu-item-discuss=Diskussion
 menu-item-report-bug=Fehler melden
@@ -10,19 +15,55 @@ menu-item-auto-scroll=Automatisches Scrollen
 menu-item-scroll-speed-slow=Langsam scrollen
 menu-item-scroll-speed-medium=Mittel scrollen
 menu-item-scroll-speed-fast=Schnell scrollen
+menu-item-start-slideshow=Diashow starten
+menu-item-stop-slideshow=Diashow beenden
+menu-item-monitor-settings=Monitoreinstellungen…
+menu-item-zoom=Zoom
+menu-item-slideshow-settings=Diashow-Einstellungen
+menu-item-playback=Modem-Wiedergabe
 menu-upgrade_version=Neue Version { $version }
 
+tooltip-back=Zurück
+tooltip-forward=Vorwärts
+tooltip-bookmarks=Lesezeichen und zuletzt besuchte Orte
+menu-item-add-bookmark=Ordner als Lesezeichen speichern
+menu-item-remove-bookmark=Lesezeichen entfernen
+menu-item-quick-jump=Schnellsprung…
+menu-label-bookmarks=Lesezeichen
+menu-label-recent=Zuletzt besucht
+quick-jump-title=Schnellsprung
+quick-jump-hint-text=Lesezeichen und zuletzt besuchte Orte filtern
+quick-jump-empty=Keine passenden Orte
 tooltip-refresh=Neu laden
+tooltip-grid-view=Vorschaubilder anzeigen
+tooltip-table-view=Tabelle anzeigen
+tooltip-list-view=Liste anzeigen
 tooltip-reset-filter-button=Filter zurücksetzen
+tooltip-find-previous=Vorheriger Treffer
+tooltip-find-next=Nächster Treffer
+tooltip-playback-restart=Neu starten
+tooltip-playback-play=Abspielen
+tooltip-playback-pause=Pause
+tooltip-playback-step=Nächste Zeile
+tooltip-playback-close=Ganze Datei anzeigen
+tooltip-recursive-search=In allen Unterordnern und Archiven suchen
+tooltip-show-in-folder=Im Ordner anzeigen
+tooltip-filter-syntax=Nach Namen oder SAUCE-Feldern filtern, z.B. author:Name group:Name year:1996 type:ansi
+tooltip-loading-sauce=SAUCE-Informationen werden gelesen…
 
 filter-entries-hint-text=Einträge filtern
 
 message-loading-image=Bild wird geladen…
+message-reading-file={ $name } wird gelesen…
+message-parsing-file={ $name } wird verarbeitet…
 message-file-not-supported=Die Datei { $name } wird nicht unterstützt.
 button-load-anyways=Trotzdem laden
 button-ok=Ok
 button-cancel=Abbrechen
 button-open=Öffnen
+button-save=Speichern
+button-search=Suchen
+button-stop=Stopp
 message-empty=Hier gibt es nichts zu sehen, bis etwas ausgewählt wurde.
 
 error-invalid-path=Ungültiger Pfad
@@ -36,16 +77,193 @@ sauce-dialog-comments-label=Kommentare:
 sauce-dialog-date-label=Datum:
 sauce-dialog-flags-label=Flags:
 sauce-dialog-font-name=Font:
+sauce-dialog-flag-ice=ICE-Farben
+sauce-dialog-flag-letter-spacing=9px-Font
+sauce-dialog-flag-aspect-ratio=Seitenverhältnis
+sauce-dialog-remove-button=SAUCE entfernen
+sauce-dialog-read-only=Änderungen können nicht gespeichert werden: { $reason }
+sauce-field-title=Titel
+sauce-field-author=Autor
+sauce-field-group=Gruppe
+sauce-field-font=Font
+sauce-field-comment-line=Kommentarzeile { $line }
+sauce-error-too-long={ $field } ist { $len } Zeichen lang, erlaubt sind höchstens { $max }
+sauce-error-not-cp437={ $field } enthält '{ $character }', das nicht im CP437-Zeichensatz vorkommt
+sauce-error-font-ascii=Fontnamen müssen ASCII sein
+sauce-error-too-many-comments=Es sind höchstens { $max } Kommentarzeilen erlaubt
+sauce-error-invalid-date=Ungültiges Datum '{ $date }', erwartet wird JJJJ-MM-TT
+sauce-error-no-record=Die Datei hat keinen SAUCE-Eintrag
+sauce-error-in-memory=Die Datei liegt nur im Speicher.
+sauce-error-read-only=Die Datei ist schreibgeschützt.
+sauce-error-nested-archive=Dateien in verschachtelten Archiven können nicht geändert werden, bitte zuerst das innere Archiv entpacken.
+sauce-error-archive-format=Dateien in { $format }-Archiven können nicht geändert werden.
 
 help-dialog-title=Hilfe
-help-dialog-text=
-    F1 - Hilfe
-    F2 - Automatisches Scrollen an/aus
-    F3 - Geschwindigeit umstellen
-    F4 - Sauce-Informationen anzeigen
+help-dialog-mouse-text=
+    Strg+Mausrad - Vergrößern/Verkleinern
+    Rechtsklick - Font, Palette und Darstellung ändern
+    Strg+Klick/Umschalt+Klick - Dateien markieren
+    Maustasten Zurück/Vorwärts - Zurück/vorwärts im Ordnerverlauf
+    Tastenkürzel lassen sich in der Tabelle [keys] der options.toml ändern, z.B. quick_jump = "Ctrl+K"
+
+keymap-show-help=Hilfe
+keymap-toggle-auto-scroll=Automatisches Scrollen an/aus
+keymap-change-scroll-speed=Geschwindigkeit umstellen
+keymap-show-sauce=Sauce-Informationen anzeigen
+keymap-toggle-slideshow=Diashow starten/beenden
+keymap-toggle-playback=Modem-Wiedergabe
+keymap-toggle-full-screen=Vollbild an/aus
+keymap-close-dialog=Geöffneten Dialog schließen
+keymap-quit=Beenden
+keymap-parent-folder=Übergeordneter Ordner
+keymap-back=Zurück im Ordnerverlauf
+keymap-forward=Vorwärts im Ordnerverlauf
+keymap-quick-jump=Schnellsprung zu einem Lesezeichen oder zuletzt besuchten Ort
+keymap-select-previous=Vorherige Datei auswählen
+keymap-select-next=Nächste Datei auswählen
+keymap-select-left=Linke Datei im Raster auswählen
+keymap-select-right=Rechte Datei im Raster auswählen
+keymap-select-first=Erste Datei auswählen
+keymap-select-last=Letzte Datei auswählen
+keymap-select-page-up=Eine Seite nach oben auswählen
+keymap-select-page-down=Eine Seite nach unten auswählen
+keymap-open=Ausgewählte Datei oder Ordner öffnen
+keymap-find=In Datei suchen
+keymap-find-next=Nächster Treffer bei geöffneter Suche
+keymap-find-previous=Vorheriger Treffer bei geöffneter Suche
+keymap-zoom-in=Vergrößern
+keymap-zoom-out=Verkleinern
+keymap-zoom-reset=Originalgröße
+keymap-scroll-top=Zum Anfang scrollen
+keymap-scroll-bottom=Zum Ende scrollen
+keymap-scroll-up=Nach oben scrollen
+keymap-scroll-down=Nach unten scrollen
+keymap-scroll-page-up=Eine Seite nach oben scrollen
+keymap-scroll-page-down=Eine Seite nach unten scrollen
+keymap-save-as=In anderem Format speichern
+keymap-export-png=Als PNG speichern
+keymap-export-png-2x=Als PNG in 2x speichern
+keymap-export-gif=Als animiertes GIF speichern
+keymap-copy-image=Bild kopieren
+keymap-toggle-mark=Ausgewählte Datei markieren
+keymap-copy-files=Markierte Dateien kopieren
+keymap-move-files=Markierte Dateien verschieben
+keymap-rename-file=Ausgewählte Datei umbenennen
+keymap-trash-files=Markierte Dateien in den Papierkorb verschieben
 
 toast-auto-scroll-on=Automatisches Scrollen an
 toast-auto-scroll-off=Automatisches Scrollen aus
 toast-scroll-slow=Scrollgeschwindigkeit: langsam
 toast-scroll-medium=Scrollgeschwindigkeit: mittel
-toast-scroll-fast=Scrollgeschwindigkeit: schnell
\ No newline at end of file
+toast-scroll-fast=Scrollgeschwindigkeit: schnell
+toast-sauce-written=SAUCE gespeichert
+toast-sauce-write-error=Fehler beim Speichern von SAUCE: { $error }
+toast-slideshow-empty=Keine Dateien zum Anzeigen
+toast-slideshow-finished=Diashow beendet
+toast-playback-not-supported=Die Wiedergabe gibt es nur für ANSI-, ASCII-, PCBoard- und Avatar-Dateien
+toast-buffer-menu-error=Fehler beim Ändern der Anzeige: { $error }
+toast-export-done=Export abgeschlossen
+toast-export-error=Fehler beim Exportieren: { $error }
+toast-converted=Konvertierte Dateien: { $count }
+toast-converted-skipped=Dateien konvertiert: { $count }, übersprungen: { $skipped }, siehe Log
+toast-convert-error=Fehler beim Konvertieren: { $error }
+toast-task-cancelled=Abgebrochen
+
+search-hint-text=Dateinamen, SAUCE-Felder oder Text suchen
+search-content-checkbox=Im Text suchen
+label-search-status={ $found } gefunden, { $scanned } durchsucht
+label-listing-status=Ordner wird gelesen… { $count } Einträge
+label-export-status=Exportiere… { $done }/{ $total } Bilder
+label-convert-status=Konvertiere… { $done }/{ $total } Dateien
+
+find-hint-text=In Datei suchen
+find-case-sensitive=Groß-/Kleinschreibung beachten
+find-no-matches=Keine Treffer
+find-match-count={ $current } von { $total }
+
+slideshow-delay=Sekunden pro Datei
+slideshow-wait-for-end=Warten bis das Scrollen endet
+slideshow-shuffle=Zufällige Reihenfolge
+slideshow-repeat=Wiederholen
+slideshow-recursive=Unterordner einbeziehen
+
+playback-unlimited=Unbegrenzt
+playback-baud-rate={ $rate } Baud
+
+zoom-fit-width=Breite anpassen
+zoom-fit-page=Ganze Seite
+zoom-fixed=Zoom
+zoom-integer-scaling=Nur ganzzahlig skalieren
+
+monitor-dialog-title=Monitoreinstellungen
+monitor-presets=Vorlagen:
+monitor-preset-clean=Klar
+monitor-preset-amber=Bernstein
+monitor-preset-green=Grüner Phosphor
+monitor-preset-crt=Röhre
+monitor-use-filter=Röhrenmonitor emulieren
+monitor-tint=Färbung
+monitor-tint-color=Farbe
+monitor-tint-grayscale=Graustufen
+monitor-tint-amber=Bernstein
+monitor-tint-green=Grün
+monitor-brightness=Helligkeit
+monitor-contrast=Kontrast
+monitor-saturation=Sättigung
+monitor-gamma=Gamma
+monitor-light=Licht
+monitor-blur=Unschärfe
+monitor-curvature=Krümmung
+monitor-scanlines=Scanlines
+
+menu-item-font=Font
+menu-item-load-font=Font laden…
+menu-item-palette=Palette
+menu-item-load-palette=Palette laden…
+palette-vga=VGA
+palette-ega=EGA
+palette-c64=C64
+palette-amiga=Amiga Workbench
+
+menu-item-export=Exportieren
+menu-item-export-png=Als PNG speichern…
+menu-item-export-png-2x=Als PNG speichern (2x)…
+menu-item-export-gif=Als animiertes GIF speichern…
+menu-item-copy-image=Bild kopieren
+error-nothing-to-export=Nichts zu exportieren
+error-gif-not-supported=Der GIF-Export braucht eine icyanim-Datei oder eine Datei, die die Modem-Wiedergabe unterstützt
+error-task-running=Bitte warten, bis der laufende Export oder die Konvertierung fertig ist
+
+menu-item-save-as=Speichern unter…
+convert-dialog-title=Speichern unter
+convert-dialog-format-label=Format:
+convert-dialog-options-label=Optionen:
+convert-dialog-save-sauce=SAUCE schreiben
+convert-dialog-compress=Komprimieren
+convert-dialog-line-length=Zeilen umbrechen nach
+convert-dialog-source-label=Konvertieren:
+convert-dialog-source-file=Angezeigte Datei
+convert-dialog-source-folder=Alle Dateien in diesem Ordner
+
+label-marked-files={ $count } markiert
+menu-item-copy-to=Kopieren nach…
+menu-item-move-to=Verschieben nach…
+menu-item-rename=Umbenennen…
+menu-item-trash=In den Papierkorb
+menu-item-extract-to=Entpacken nach…
+file-operation-copy-title=Kopieren
+file-operation-move-title=Verschieben
+file-operation-rename-title=Umbenennen
+file-operation-trash-title=In den Papierkorb
+file-operation-extract-title=Entpacken
+file-operation-copy-question={ $count } Einträge nach { $destination } kopieren?
+file-operation-move-question={ $count } Einträge nach { $destination } verschieben?
+file-operation-rename-question=Neuer Name:
+file-operation-trash-question={ $count } Einträge in den Papierkorb verschieben?
+file-operation-extract-question={ $count } Einträge nach { $destination } entpacken?
+file-operation-more-files=…und { $count } weitere
+toast-file-operation-done=Fertig
+toast-file-operation-error=Fehler: { $error }
+file-error-no-file-name={ $path } hat keinen Dateinamen
+file-error-exists={ $path } existiert bereits
+file-error-into-itself={ $path } kann nicht in sich selbst abgelegt werden
@@ -2,6 +2,11 @@ heading-title=Title
 heading-author=Author
 heading-group=Group
 heading-screen-mode=Flags
+heading-name=Name
+heading-size=Size
+heading-modified=Modified
+heading-date=Date
+heading-dimensions=Size (chars)
 
 menu-item-discuss=Discuss
 menu-item-report-bug=Report a bug
@@ -10,19 +15,55 @@ menu-item-auto-scroll=Auto scroll
 menu-item-scroll-speed-slow=Slow speed
 menu-item-scroll-speed-medium=Medium speed
 menu-item-scroll-speed-fast=Fast speed
+menu-item-start-slideshow=Start slideshow
+menu-item-stop-slideshow=Stop slideshow
+menu-item-monitor-settings=Monitor settings…
+menu-item-zoom=Zoom
+menu-item-slideshow-settings=Slideshow settings
+menu-item-playback=Baud rate playback
 menu-upgrade_version=Upgrade to { $version }
 
+tooltip-back=Back
+tooltip-forward=Forward
+tooltip-bookmarks=Bookmarks and recent locations
+menu-item-add-bookmark=Bookmark this folder
+menu-item-remove-bookmark=Remove bookmark
+menu-item-quick-jump=Quick jump…
+menu-label-bookmarks=Bookmarks
+menu-label-recent=Recent
+quick-jump-title=Quick jump
+quick-jump-hint-text=Filter bookmarks and recent locations
+quick-jump-empty=No matching locations
 tooltip-refresh=Refresh
+tooltip-grid-view=Show thumbnails
+tooltip-table-view=Show table
+tooltip-list-view=Show list
 tooltip-reset-filter-button=Reset filter
+tooltip-find-previous=Previous match
+tooltip-find-next=Next match
+tooltip-playback-restart=Restart
+tooltip-playback-play=Play
+tooltip-playback-pause=Pause
+tooltip-playback-step=Next line
+tooltip-playback-close=Show the whole file
+tooltip-recursive-search=Search in all sub folders and archives
+tooltip-show-in-folder=Show in folder
+tooltip-filter-syntax=Filter by name or SAUCE fields, e.g. author:name group:name year:1996 type:ansi
+tooltip-loading-sauce=Reading SAUCE information…
 
 filter-entries-hint-text=Filter entries
 
 message-loading-image=Loading image…
+message-reading-file=Reading { $name }…
+message-parsing-file=Parsing { $name }…
 message-file-not-supported=File { $name } may not be supported.
 button-load-anyways=Load anyways
 button-ok=OK
 button-cancel=Cancel
 button-open=Open
+button-save=Save
+button-search=Search
+button-stop=Stop
 message-empty=Here you see nothing until you select a supported file.
 
 error-invalid-path=Invalid path
@@ -36,16 +77,193 @@ sauce-dialog-comments-label=Comments:
 sauce-dialog-date-label=Date:
 sauce-dialog-flags-label=Flags:
 sauce-dialog-font-name=Font:
+sauce-dialog-flag-ice=ICE colors
+sauce-dialog-flag-letter-spacing=9px font
+sauce-dialog-flag-aspect-ratio=Aspect ratio
+sauce-dialog-remove-button=Remove SAUCE
+sauce-dialog-read-only=Changes can't be saved: { $reason }
+sauce-field-title=Title
+sauce-field-author=Author
+sauce-field-group=Group
+sauce-field-font=Font
+sauce-field-comment-line=Comment line { $line }
+sauce-error-too-long={ $field } is { $len } characters long, the maximum is { $max }
+sauce-error-not-cp437={ $field } contains '{ $character }' which is not in the CP437 character set
+sauce-error-font-ascii=Font names must be ASCII
+sauce-error-too-many-comments=Only { $max } comment lines are allowed
+sauce-error-invalid-date=Invalid date '{ $date }', expected YYYY-MM-DD
+sauce-error-no-record=File has no SAUCE record
+sauce-error-in-memory=The file is only held in memory.
+sauce-error-read-only=The file is read only.
+sauce-error-nested-archive=Files inside nested archives can't be changed, extract the inner archive first.
+sauce-error-archive-format=Changing files inside { $format } archives is not supported.
 
 help-dialog-title=Help
-help-dialog-text=
-    F1 - Help 
-    F2 - Toggle auto scrolling
-    F3 - Toggle scroll speed 
-    F4 - Show sauce info
+help-dialog-mouse-text=
+    Ctrl+mouse wheel - Zoom in/out
+    Right click - Change font, palette and display flags
+    Ctrl+click/Shift+click - Mark files
+    Mouse back/forward buttons - Back/forward in the folder history
+    Shortcuts can be changed in the [keys] table of options.toml, e.g. quick_jump = "Ctrl+K"
+
+keymap-show-help=Help
+keymap-toggle-auto-scroll=Toggle auto scrolling
+keymap-change-scroll-speed=Toggle scroll speed
+keymap-show-sauce=Show sauce info
+keymap-toggle-slideshow=Start/stop slideshow
+keymap-toggle-playback=Baud rate playback
+keymap-toggle-full-screen=Toggle full screen
+keymap-close-dialog=Close the open dialog
+keymap-quit=Quit
+keymap-parent-folder=Parent folder
+keymap-back=Back in the folder history
+keymap-forward=Forward in the folder history
+keymap-quick-jump=Quick jump to a bookmark or recent location
+keymap-select-previous=Select the previous file
+keymap-select-next=Select the next file
+keymap-select-left=Select the file to the left in the grid
+keymap-select-right=Select the file to the right in the grid
+keymap-select-first=Select the first file
+keymap-select-last=Select the last file
+keymap-select-page-up=Select one page up
+keymap-select-page-down=Select one page down
+keymap-open=Open the selected file or folder
+keymap-find=Find in file
+keymap-find-next=Next match while the find bar is open
+keymap-find-previous=Previous match while the find bar is open
+keymap-zoom-in=Zoom in
+keymap-zoom-out=Zoom out
+keymap-zoom-reset=Original size
+keymap-scroll-top=Scroll to the top
+keymap-scroll-bottom=Scroll to the bottom
+keymap-scroll-up=Scroll up
+keymap-scroll-down=Scroll down
+keymap-scroll-page-up=Scroll one page up
+keymap-scroll-page-down=Scroll one page down
+keymap-save-as=Save as another format
+keymap-export-png=Save as PNG
+keymap-export-png-2x=Save as PNG at 2x
+keymap-export-gif=Save as animated GIF
+keymap-copy-image=Copy image
+keymap-toggle-mark=Mark the selected file
+keymap-copy-files=Copy the marked files
+keymap-move-files=Move the marked files
+keymap-rename-file=Rename the selected file
+keymap-trash-files=Move the marked files to the trash
 
 toast-auto-scroll-on=Auto scroll on
 toast-auto-scroll-off=Auto scroll off
 toast-scroll-slow=Scroll speed: slow
 toast-scroll-medium=Scroll speed: medium
-toast-scroll-fast=Scroll speed: fast
\ No newline at end of file
+toast-scroll-fast=Scroll speed: fast
+toast-sauce-written=SAUCE saved
+toast-sauce-write-error=Error saving SAUCE: { $error }
+toast-slideshow-empty=No files to show
+toast-slideshow-finished=Slideshow finished
+toast-playback-not-supported=Playback is only available for ANSI, ASCII, PCBoard and Avatar files
+toast-buffer-menu-error=Error changing the display: { $error }
+toast-export-done=Export finished
+toast-export-error=Error exporting: { $error }
+toast-converted=Files converted: { $count }
+toast-converted-skipped=Files converted: { $count }, skipped: { $skipped }, see the log
+toast-convert-error=Error converting: { $error }
+toast-task-cancelled=Stopped
+
+search-hint-text=Search file names, SAUCE fields or text
+search-content-checkbox=Search text content
+label-search-status={ $found } found, { $scanned } scanned
+label-listing-status=Reading folder… { $count } entries
+label-export-status=Exporting… { $done }/{ $total } frames
+label-convert-status=Converting… { $done }/{ $total } files
+
+find-hint-text=Find in file
+find-case-sensitive=Match case
+find-no-matches=No matches
+find-match-count={ $current } of { $total }
+
+slideshow-delay=Seconds per file
+slideshow-wait-for-end=Wait until scrolling ends
+slideshow-shuffle=Shuffle
+slideshow-repeat=Loop
+slideshow-recursive=Include sub folders
+
+playback-unlimited=Unlimited
+playback-baud-rate={ $rate } baud
+
+zoom-fit-width=Fit width
+zoom-fit-page=Fit page
+zoom-fixed=Zoom
+zoom-integer-scaling=Integer scaling only
+
+monitor-dialog-title=Monitor settings
+monitor-presets=Presets:
+monitor-preset-clean=Clean
+monitor-preset-amber=Amber
+monitor-preset-green=Green phosphor
+monitor-preset-crt=CRT
+monitor-use-filter=CRT emulation
+monitor-tint=Tint
+monitor-tint-color=Color
+monitor-tint-grayscale=Grayscale
+monitor-tint-amber=Amber
+monitor-tint-green=Green
+monitor-brightness=Brightness
+monitor-contrast=Contrast
+monitor-saturation=Saturation
+monitor-gamma=Gamma
+monitor-light=Light
+monitor-blur=Blur
+monitor-curvature=Curvature
+monitor-scanlines=Scanlines
+
+menu-item-font=Font
+menu-item-load-font=Load font…
+menu-item-palette=Palette
+menu-item-load-palette=Load palette…
+palette-vga=VGA
+palette-ega=EGA
+palette-c64=C64
+palette-amiga=Amiga Workbench
+
+menu-item-export=Export
+menu-item-export-png=Save as PNG…
+menu-item-export-png-2x=Save as PNG (2x)…
+menu-item-export-gif=Save as animated GIF…
+menu-item-copy-image=Copy image
+error-nothing-to-export=Nothing to export
+error-gif-not-supported=GIF export needs an icyanim file or a file that supports baud rate playback
+error-task-running=Wait for the running export or conversion to finish
+
+menu-item-save-as=Save as…
+convert-dialog-title=Save as
+convert-dialog-format-label=Format:
+convert-dialog-options-label=Options:
+convert-dialog-save-sauce=Write SAUCE
+convert-dialog-compress=Compress
+convert-dialog-line-length=Wrap lines after
+convert-dialog-source-label=Convert:
+convert-dialog-source-file=Shown file
+convert-dialog-source-folder=All files in this folder
+
+label-marked-files={ $count } marked
+menu-item-copy-to=Copy to…
+menu-item-move-to=Move to…
+menu-item-rename=Rename…
+menu-item-trash=Move to trash
+menu-item-extract-to=Extract to…
+file-operation-copy-title=Copy
+file-operation-move-title=Move
+file-operation-rename-title=Rename
+file-operation-trash-title=Move to trash
+file-operation-extract-title=Extract
+file-operation-copy-question=Copy { $count } entries to { $destination }?
+file-operation-move-question=Move { $count } entries to { $destination }?
+file-operation-rename-question=New name:
+file-operation-trash-question=Move { $count } entries to the trash?
+file-operation-extract-question=Extract { $count } entries to { $destination }?
+file-operation-more-files=…and { $count } more
+toast-file-operation-done=Done
+toast-file-operation-error=Error: { $error }
+file-error-no-file-name={ $path } has no file name
+file-error-exists={ $path } already exists
+file-error-into-itself={ $path } can't be put into itself
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use unarj_rs::arj_archive::ArjArchieve;
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource};
+
+pub struct ArjFormat;
+
+impl ArchiveFormat for ArjFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let mut archive = ArjArchieve::new(source.open()?)?;
+        let mut result = Vec::new();
+        while let Some(header) = archive.get_next_entry()? {
+            archive.skip(&header)?;
+            result.push(ArchiveItem {
+                index: result.len(),
+                path: PathBuf::from(header.name.replace('\\', "/")),
+                is_dir: false,
+                size: header.original_size as u64,
+            });
+        }
+        Ok(result)
+    }
+
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        let mut archive = ArjArchieve::new(source.open()?)?;
+        let mut index = 0;
+        while let Some(header) = archive.get_next_entry()? {
+            if !on_entry(index, Ok(archive.read(&header)?)) {
+                break;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn is_sequential(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::Read;
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource};
+
+pub struct LhaFormat;
+
+impl ArchiveFormat for LhaFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let mut reader = delharc::LhaDecodeReader::new(source.open()?).map_err(|err| anyhow::anyhow!("{err}"))?;
+        let mut result = Vec::new();
+        loop {
+            let header = reader.header();
+            result.push(ArchiveItem {
+                index: result.len(),
+                path: header.parse_pathname(),
+                is_dir: header.is_directory(),
+                size: header.original_size,
+            });
+            if !reader.next_file()? {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        let mut reader = delharc::LhaDecodeReader::new(source.open()?).map_err(|err| anyhow::anyhow!("{err}"))?;
+        let mut index = 0;
+        loop {
+            if !reader.header().is_directory() && !on_entry(index, read_file(&mut reader)) {
+                break;
+            }
+            if !reader.next_file()? {
+                break;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn is_sequential(&self) -> bool {
+        true
+    }
+}
+
+fn read_file<R: Read>(reader: &mut delharc::LhaDecodeReader<R>) -> anyhow::Result<Vec<u8>> {
+    if !reader.is_decoder_supported() {
+        return Err(anyhow::anyhow!(
+            "Unsupported compression method in {}",
+            reader.header().parse_pathname().display()
+        ));
+    }
+    let mut data = Vec::with_capacity(reader.header().original_size as usize);
+    reader.read_to_end(&mut data)?;
+    reader.crc_check()?;
+    Ok(data)
+}
@@ -0,0 +1,289 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, Cursor, Read, Seek},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use i18n_embed_fl::fl;
+use once_cell::sync::Lazy;
+
+mod arj_archive;
+mod lha_archive;
+mod rar_archive;
+mod sevenz_archive;
+mod tar_archive;
+mod zip_archive;
+
+pub use rar_archive::remove_temp_files;
+
+/// Upper bound for decompressed archive data kept in memory.
+const CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+static CACHE: Lazy<Mutex<ArchiveCache>> = Lazy::new(|| Mutex::new(ArchiveCache::default()));
+
+/// A file or folder listed by an archive backend.
+pub struct ArchiveItem {
+    /// Index the backend reads and writes the entry with.
+    pub index: usize,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+/// Backend for one archive format.
+/// `index` is the [`ArchiveItem::index`] of an entry returned by `list`.
+pub trait ArchiveFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>>;
+
+    /// Reads one entry, formats without `read` find it with `read_all`.
+    fn read(&self, source: &ArchiveSource, index: usize) -> anyhow::Result<Vec<u8>> {
+        let mut result = None;
+        self.read_all(source, &mut |i, data| {
+            if i == index {
+                result = Some(data);
+            }
+            result.is_none()
+        })?;
+        result.unwrap_or_else(|| Err(anyhow::anyhow!("Entry {index} not found")))
+    }
+
+    /// Decodes the files front to back, `on_entry` gets the index and data of each and returns `false` to stop.
+    /// An entry that can't be decoded is passed as error, the other entries are still read.
+    /// Formats implement at least one of `read` and `read_all`.
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        for item in self.list(source)? {
+            if !item.is_dir && !on_entry(item.index, self.read(source, item.index)) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Solid and stream based archives can't seek to an entry, reading one decodes everything before it.
+    /// Their entries are decoded in one pass and cached together.
+    fn is_sequential(&self) -> bool {
+        false
+    }
+
+    /// Replaces the data of an entry, only called for archives on disk.
+    fn write(&self, _source: &ArchiveSource, _index: usize, _data: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!("Writing is not supported for this archive format"))
+    }
+
+    fn can_write(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the backend for an archive file name, `None` if it's not a supported archive.
+pub fn get_format(path: &Path) -> Option<Box<dyn ArchiveFormat>> {
+    let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        return Some(Box::new(tar_archive::TarFormat { gzip: true }));
+    }
+
+    match name.rsplit('.').next()? {
+        "zip" => Some(Box::new(zip_archive::ZipFormat)),
+        "rar" => Some(Box::new(rar_archive::RarFormat)),
+        "7z" => Some(Box::new(sevenz_archive::SevenZFormat)),
+        "tar" => Some(Box::new(tar_archive::TarFormat { gzip: false })),
+        "lha" | "lzh" => Some(Box::new(lha_archive::LhaFormat)),
+        "arj" => Some(Box::new(arj_archive::ArjFormat)),
+        _ => None,
+    }
+}
+
+pub fn is_archive(path: &Path) -> bool {
+    get_format(path).is_some()
+}
+
+pub trait ReadSeek: Read + Seek {}
+impl<T: Read + Seek> ReadSeek for T {}
+
+struct SharedData(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedData {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Where the bytes of an archive come from, archives inside archives are held in memory.
+#[derive(Clone, Debug)]
+pub enum ArchiveSource {
+    File(PathBuf),
+    Memory { name: PathBuf, data: Arc<Vec<u8>> },
+}
+
+impl PartialEq for ArchiveSource {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ArchiveSource::File(a), ArchiveSource::File(b)) => a == b,
+            (ArchiveSource::Memory { data: a, .. }, ArchiveSource::Memory { data: b, .. }) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl ArchiveSource {
+    /// File name used to pick the archive backend.
+    pub fn name(&self) -> &Path {
+        match self {
+            ArchiveSource::File(path) => path,
+            ArchiveSource::Memory { name, .. } => name,
+        }
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn ReadSeek>> {
+        match self {
+            ArchiveSource::File(path) => Ok(Box::new(File::open(path)?)),
+            ArchiveSource::Memory { data, .. } => Ok(Box::new(Cursor::new(SharedData(data.clone())))),
+        }
+    }
+
+    fn get_format(&self) -> anyhow::Result<Box<dyn ArchiveFormat>> {
+        get_format(self.name()).ok_or_else(|| anyhow::anyhow!("Unsupported archive {}", self.name().display()))
+    }
+
+    /// Lists the archive directory without decompressing anything.
+    pub fn list(&self) -> anyhow::Result<Vec<(PathBuf, bool, ArchiveEntry)>> {
+        let items = self.get_format()?.list(self)?;
+        Ok(items
+            .into_iter()
+            .map(|item| {
+                let entry = ArchiveEntry {
+                    source: self.clone(),
+                    index: item.index,
+                    size: item.size,
+                };
+                (item.path, item.is_dir, entry)
+            })
+            .collect())
+    }
+
+    fn read_entry(&self, index: usize) -> anyhow::Result<Arc<Vec<u8>>> {
+        let format = self.get_format()?;
+        if !format.is_sequential() {
+            let data = Arc::new(format.read(self, index)?);
+            CACHE.lock().unwrap().insert(self, index, data.clone());
+            return Ok(data);
+        }
+
+        // the SAUCE loader reads every entry, decoding up to each one would be quadratic.
+        // Stop at half the cache size past the entry so the following ones don't get evicted by the same pass.
+        let mut result = None;
+        let mut read_after = 0;
+        format.read_all(self, &mut |i, data| {
+            let data = data.map(Arc::new);
+            if let Ok(data) = &data {
+                CACHE.lock().unwrap().insert(self, i, data.clone());
+            }
+            if i == index {
+                result = Some(data);
+            }
+            if let Some(Ok(data)) = &result {
+                read_after += data.len();
+            }
+            read_after < CACHE_SIZE / 2
+        })?;
+        result.unwrap_or_else(|| Err(anyhow::anyhow!("Entry {index} not found")))
+    }
+}
+
+/// Location of a file inside an archive, the data is decompressed on demand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchiveEntry {
+    pub source: ArchiveSource,
+    pub index: usize,
+    pub size: u64,
+}
+
+impl ArchiveEntry {
+    pub fn read_data(&self) -> anyhow::Result<Arc<Vec<u8>>> {
+        if let Some(data) = CACHE.lock().unwrap().get(&self.source, self.index) {
+            return Ok(data);
+        }
+
+        self.source.read_entry(self.index)
+    }
+
+    /// Checks if the entry can be changed, returns the reason if not.
+    pub fn check_writable(&self) -> Result<(), String> {
+        let ArchiveSource::File(path) = &self.source else {
+            return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-nested-archive"));
+        };
+        match get_format(path) {
+            Some(format) if format.can_write() => Ok(()),
+            _ => Err(fl!(
+                crate::LANGUAGE_LOADER,
+                "sauce-error-archive-format",
+                format = path.extension().unwrap_or_default().to_string_lossy().to_string()
+            )),
+        }
+    }
+
+    pub fn write_data(&self, data: &[u8]) -> anyhow::Result<()> {
+        self.check_writable().map_err(|err| anyhow::anyhow!(err))?;
+        self.source.get_format()?.write(&self.source, self.index, data)?;
+        CACHE.lock().unwrap().remove_source(&self.source);
+        Ok(())
+    }
+}
+
+/// An opened archive in the navigation stack of the file view.
+#[derive(Clone)]
+pub struct ArchiveLevel {
+    pub source: ArchiveSource,
+    /// Path of this archive inside the parent archive, `None` for archives on disk.
+    pub entry_path: Option<PathBuf>,
+    /// Folder inside the archive that's currently shown.
+    pub folder: PathBuf,
+}
+
+impl ArchiveLevel {
+    pub fn new(source: ArchiveSource, entry_path: Option<PathBuf>) -> Self {
+        Self {
+            source,
+            entry_path,
+            folder: PathBuf::new(),
+        }
+    }
+}
+
+/// Decompressed entries by source and index.
+#[derive(Default)]
+struct ArchiveCache {
+    entries: VecDeque<(ArchiveSource, usize, Arc<Vec<u8>>)>,
+    size: usize,
+}
+
+impl ArchiveCache {
+    fn get(&mut self, source: &ArchiveSource, index: usize) -> Option<Arc<Vec<u8>>> {
+        let pos = self.entries.iter().position(|(s, i, _)| *i == index && s == source)?;
+        // move to the back, the front gets evicted first
+        let item = self.entries.remove(pos)?;
+        let data = item.2.clone();
+        self.entries.push_back(item);
+        Some(data)
+    }
+
+    fn remove_source(&mut self, source: &ArchiveSource) {
+        self.entries.retain(|(s, _, _)| s != source);
+        self.size = self.entries.iter().map(|(_, _, data)| data.len()).sum();
+    }
+
+    fn insert(&mut self, source: &ArchiveSource, index: usize, data: Arc<Vec<u8>>) {
+        if self.entries.iter().any(|(s, i, _)| *i == index && s == source) {
+            return;
+        }
+        self.size += data.len();
+        self.entries.push_back((source.clone(), index, data));
+        while self.size > CACHE_SIZE && self.entries.len() > 1 {
+            if let Some((_, _, data)) = self.entries.pop_front() {
+                self.size -= data.len();
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource};
+
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+static TEMP_FILES: Lazy<Mutex<Vec<TempFile>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+pub struct RarFormat;
+
+/// unrar only works on files, archives held in memory get written to a temporary file.
+/// It's written once per source and removed when the archive data is dropped.
+struct TempFile {
+    data: Weak<Vec<u8>>,
+    path: PathBuf,
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            log::error!("Error removing temporary file {}: {err}", self.path.display());
+        }
+    }
+}
+
+fn get_path(source: &ArchiveSource) -> anyhow::Result<PathBuf> {
+    let data = match source {
+        ArchiveSource::File(path) => return Ok(path.clone()),
+        ArchiveSource::Memory { data, .. } => data,
+    };
+
+    let mut temp_files = TEMP_FILES.lock().unwrap();
+    temp_files.retain(|file| file.data.strong_count() > 0);
+    if let Some(file) = temp_files.iter().find(|file| file.data.as_ptr() == Arc::as_ptr(data)) {
+        return Ok(file.path.clone());
+    }
+
+    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("icy_view_{}_{counter}.rar", std::process::id()));
+    fs::write(&path, data.as_slice())?;
+    temp_files.push(TempFile {
+        data: Arc::downgrade(data),
+        path: path.clone(),
+    });
+    Ok(path)
+}
+
+/// Removes the temporary files of archives that are still open, called on exit.
+pub fn remove_temp_files() {
+    TEMP_FILES.lock().unwrap().clear();
+}
+
+impl ArchiveFormat for RarFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let path = get_path(source)?;
+        let mut result = Vec::new();
+        for entry in unrar::Archive::new(&path).open_for_listing()? {
+            let entry = entry?;
+            result.push(ArchiveItem {
+                index: result.len(),
+                path: entry.filename.clone(),
+                is_dir: entry.is_directory(),
+                size: entry.unpacked_size,
+            });
+        }
+        Ok(result)
+    }
+
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        let path = get_path(source)?;
+        let mut archive = unrar::Archive::new(&path).open_for_processing()?;
+        let mut index = 0;
+        while let Some(header) = archive.read_header()? {
+            if header.entry().is_directory() {
+                archive = header.skip()?;
+            } else {
+                let (data, next) = header.read()?;
+                if !on_entry(index, Ok(data)) {
+                    break;
+                }
+                archive = next;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn is_sequential(&self) -> bool {
+        true
+    }
+}
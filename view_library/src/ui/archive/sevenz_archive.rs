@@ -0,0 +1,57 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use sevenz_rust::{Password, SevenZReader};
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource, ReadSeek};
+
+pub struct SevenZFormat;
+
+impl SevenZFormat {
+    fn open(&self, source: &ArchiveSource) -> anyhow::Result<SevenZReader<Box<dyn ReadSeek>>> {
+        let mut reader = source.open()?;
+        let len = reader.seek(SeekFrom::End(0))?;
+        reader.rewind()?;
+        Ok(SevenZReader::new(reader, len, Password::empty())?)
+    }
+}
+
+impl ArchiveFormat for SevenZFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let reader = self.open(source)?;
+        // for_each_entries decodes the files with data first and then the empty ones, the index counts in that order
+        let files = &reader.archive().files;
+        Ok(files
+            .iter()
+            .filter(|entry| entry.has_stream)
+            .chain(files.iter().filter(|entry| !entry.has_stream))
+            .enumerate()
+            .map(|(index, entry)| ArchiveItem {
+                index,
+                path: entry.name().into(),
+                is_dir: entry.is_directory(),
+                size: entry.size(),
+            })
+            .collect())
+    }
+
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        let mut reader = self.open(source)?;
+        // solid archives can only be decoded front to back
+        let mut index = 0;
+        reader.for_each_entries(|entry, data| {
+            let i = index;
+            index += 1;
+            if entry.is_directory() {
+                return Ok(true);
+            }
+            let mut buf = Vec::with_capacity(entry.size() as usize);
+            data.read_to_end(&mut buf)?;
+            Ok(on_entry(i, Ok(buf)))
+        })?;
+        Ok(())
+    }
+
+    fn is_sequential(&self) -> bool {
+        true
+    }
+}
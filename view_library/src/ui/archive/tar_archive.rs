@@ -0,0 +1,56 @@
+use std::io::Read;
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource};
+
+pub struct TarFormat {
+    pub gzip: bool,
+}
+
+impl TarFormat {
+    fn open(&self, source: &ArchiveSource) -> anyhow::Result<tar::Archive<Box<dyn Read>>> {
+        let reader: Box<dyn Read> = if self.gzip {
+            Box::new(flate2::read::GzDecoder::new(source.open()?))
+        } else {
+            Box::new(source.open()?)
+        };
+        Ok(tar::Archive::new(reader))
+    }
+}
+
+impl ArchiveFormat for TarFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let mut archive = self.open(source)?;
+        let mut result = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            result.push(ArchiveItem {
+                index: result.len(),
+                path: entry.path()?.to_path_buf(),
+                is_dir: entry.header().entry_type().is_dir(),
+                size: entry.size(),
+            });
+        }
+        Ok(result)
+    }
+
+    fn read_all(&self, source: &ArchiveSource, on_entry: &mut dyn FnMut(usize, anyhow::Result<Vec<u8>>) -> bool) -> anyhow::Result<()> {
+        let mut archive = self.open(source)?;
+        // tar has no directory, the entries need to be walked in order
+        for (index, entry) in archive.entries()?.enumerate() {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_dir() {
+                continue;
+            }
+            let mut data = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut data)?;
+            if !on_entry(index, Ok(data)) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_sequential(&self) -> bool {
+        true
+    }
+}
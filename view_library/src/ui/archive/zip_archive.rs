@@ -0,0 +1,80 @@
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
+
+use zip::{write::FileOptions, CompressionMethod};
+
+use super::{ArchiveFormat, ArchiveItem, ArchiveSource};
+use crate::ui::file_operations;
+
+pub struct ZipFormat;
+
+impl ArchiveFormat for ZipFormat {
+    fn list(&self, source: &ArchiveSource) -> anyhow::Result<Vec<ArchiveItem>> {
+        let mut archive = zip::ZipArchive::new(source.open()?)?;
+        let mut result = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            // raw access only reads the headers
+            match archive.by_index_raw(i) {
+                Ok(file) => {
+                    result.push(ArchiveItem {
+                        index: i,
+                        path: file.enclosed_name().unwrap_or(Path::new("unknown")).to_path_buf(),
+                        is_dir: file.is_dir(),
+                        size: file.size(),
+                    });
+                }
+                // the other entries are still listed with their real index
+                Err(err) => {
+                    log::error!("Skipping zip entry {i}: {err}");
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn read(&self, source: &ArchiveSource, index: usize) -> anyhow::Result<Vec<u8>> {
+        let mut archive = zip::ZipArchive::new(source.open()?)?;
+        let mut file = archive.by_index(index)?;
+        let mut data = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn write(&self, source: &ArchiveSource, index: usize, data: &[u8]) -> anyhow::Result<()> {
+        let ArchiveSource::File(path) = source else {
+            return Err(anyhow::anyhow!("Only archives on disk can be written"));
+        };
+        // the archive is closed before it gets replaced
+        file_operations::replace_file(path, |tmp_path| {
+            let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+            let mut writer = zip::ZipWriter::new(File::create(tmp_path)?);
+
+            // copy all other entries without recompressing them
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i)?;
+                if i == index {
+                    let compression = match file.compression() {
+                        CompressionMethod::Stored => CompressionMethod::Stored,
+                        _ => CompressionMethod::Deflated,
+                    };
+                    let options = FileOptions::default().compression_method(compression).last_modified_time(file.last_modified());
+                    let name = file.name().to_string();
+                    drop(file);
+                    writer.start_file(name, options)?;
+                    writer.write_all(data)?;
+                } else {
+                    writer.raw_copy_file(file)?;
+                }
+            }
+            writer.finish()?;
+            Ok(())
+        })
+    }
+
+    fn can_write(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// Progress of a background task, shared between the worker and the UI thread.
+#[derive(Clone, Default)]
+pub struct TaskProgress {
+    cancelled: Arc<AtomicBool>,
+    done: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+    skipped: Arc<AtomicUsize>,
+}
+
+impl TaskProgress {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error once the task is cancelled, the worker stops at the next step.
+    pub fn check_cancelled(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            return Err(anyhow::anyhow!("Cancelled"));
+        }
+        Ok(())
+    }
+
+    pub fn set_total(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a step that was left out, like a file that failed to convert.
+    pub fn skip(&self) {
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_skipped(&self) -> usize {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    pub fn get_done(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Returns 0 if the number of steps isn't known yet.
+    pub fn get_total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TaskKind {
+    Export,
+    Convert,
+}
+
+/// Runs a long export or conversion on a worker thread so the window stays responsive.
+pub struct BackgroundTask {
+    pub kind: TaskKind,
+    progress: TaskProgress,
+    /// Returns the number of written files.
+    handle: Option<JoinHandle<anyhow::Result<usize>>>,
+}
+
+impl BackgroundTask {
+    pub fn start(kind: TaskKind, run: impl FnOnce(&TaskProgress) -> anyhow::Result<usize> + Send + 'static) -> Self {
+        let progress = TaskProgress::default();
+        let thread_progress = progress.clone();
+        let handle = thread::spawn(move || run(&thread_progress));
+        Self {
+            kind,
+            progress,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn get_progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().map_or(true, |handle| handle.is_finished())
+    }
+
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the result once the worker is done, `None` while it's running or if it was cancelled.
+    pub fn take_result(&mut self) -> Option<anyhow::Result<usize>> {
+        if !self.is_finished() {
+            return None;
+        }
+        let result = match self.handle.take()?.join() {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("The worker thread panicked")),
+        };
+        if self.progress.is_cancelled() {
+            return None;
+        }
+        Some(result)
+    }
+}
+
+impl Drop for BackgroundTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,206 @@
+use std::{fs, path::Path};
+
+use eframe::egui::{self, Ui};
+use i18n_embed_fl::fl;
+use icy_engine::{BitFont, Color, IceMode, Palette, ANSI_FONTS, ANSI_FONT_NAMES};
+use icy_engine_gui::BufferView;
+
+const FONT_EXTENSIONS: [&str; 3] = ["psf", "f16", "fnt"];
+
+/// The DOS palette as the VGA DAC shows it, 6 bit values scaled by 4.
+const VGA_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0x00, 0x00, 0xA8),
+    (0x00, 0xA8, 0x00),
+    (0x00, 0xA8, 0xA8),
+    (0xA8, 0x00, 0x00),
+    (0xA8, 0x00, 0xA8),
+    (0xA8, 0x54, 0x00),
+    (0xA8, 0xA8, 0xA8),
+    (0x54, 0x54, 0x54),
+    (0x54, 0x54, 0xFC),
+    (0x54, 0xFC, 0x54),
+    (0x54, 0xFC, 0xFC),
+    (0xFC, 0x54, 0x54),
+    (0xFC, 0x54, 0xFC),
+    (0xFC, 0xFC, 0x54),
+    (0xFC, 0xFC, 0xFC),
+];
+
+const EGA_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0x00, 0x00, 0xAA),
+    (0x00, 0xAA, 0x00),
+    (0x00, 0xAA, 0xAA),
+    (0xAA, 0x00, 0x00),
+    (0xAA, 0x00, 0xAA),
+    (0xAA, 0x55, 0x00),
+    (0xAA, 0xAA, 0xAA),
+    (0x55, 0x55, 0x55),
+    (0x55, 0x55, 0xFF),
+    (0x55, 0xFF, 0x55),
+    (0x55, 0xFF, 0xFF),
+    (0xFF, 0x55, 0x55),
+    (0xFF, 0x55, 0xFF),
+    (0xFF, 0xFF, 0x55),
+    (0xFF, 0xFF, 0xFF),
+];
+
+const C64_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xFF, 0xFF, 0xFF),
+    (0x68, 0x37, 0x2B),
+    (0x70, 0xA4, 0xB2),
+    (0x6F, 0x3D, 0x86),
+    (0x58, 0x8D, 0x43),
+    (0x35, 0x28, 0x79),
+    (0xB8, 0xC7, 0x6F),
+    (0x6F, 0x4F, 0x25),
+    (0x43, 0x39, 0x00),
+    (0x9A, 0x67, 0x59),
+    (0x44, 0x44, 0x44),
+    (0x6C, 0x6C, 0x6C),
+    (0x9A, 0xD2, 0x84),
+    (0x6C, 0x5E, 0xB5),
+    (0x95, 0x95, 0x95),
+];
+
+/// The four Workbench 1.3 colors, repeated so every attribute maps to a color.
+const AMIGA_PALETTE: [(u8, u8, u8); 4] = [(0x00, 0x55, 0xAA), (0xFF, 0xFF, 0xFF), (0x00, 0x00, 0x22), (0xFF, 0x88, 0x00)];
+
+fn create_palette(colors: &[(u8, u8, u8)]) -> Palette {
+    let colors: Vec<Color> = (0..16)
+        .map(|i| {
+            let (r, g, b) = colors[i % colors.len()];
+            Color::new(r, g, b)
+        })
+        .collect();
+    Palette::from_slice(&colors)
+}
+
+/// Reads JASC-PAL text files and raw RGB palettes, raw palettes with values up to 63 are taken as 6 bit VGA values.
+fn parse_palette(data: &[u8]) -> anyhow::Result<Vec<Color>> {
+    if data.starts_with(b"JASC-PAL") {
+        let text = String::from_utf8_lossy(data);
+        let mut colors = Vec::new();
+        // header, version and color count
+        for line in text.lines().skip(3) {
+            let values: Vec<u8> = line.split_whitespace().filter_map(|value| value.parse().ok()).collect();
+            if let [r, g, b] = values[..] {
+                colors.push(Color::new(r, g, b));
+            }
+        }
+        if colors.is_empty() {
+            anyhow::bail!("no colors in palette");
+        }
+        return Ok(colors);
+    }
+
+    if data.len() < 48 || data.len() % 3 != 0 {
+        anyhow::bail!("unsupported palette format");
+    }
+    let six_bit = data.iter().all(|value| *value < 64);
+    let scale = |value: u8| if six_bit { (value << 2) | (value >> 4) } else { value };
+    Ok(data
+        .chunks_exact(3)
+        .map(|rgb| Color::new(scale(rgb[0]), scale(rgb[1]), scale(rgb[2])))
+        .collect())
+}
+
+fn load_font(path: &Path) -> anyhow::Result<BitFont> {
+    let data = fs::read(path)?;
+    let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    Ok(BitFont::from_bytes(name, &data)?)
+}
+
+fn load_palette(path: &Path) -> anyhow::Result<Palette> {
+    let data = fs::read(path)?;
+    Ok(Palette::from_slice(&parse_palette(&data)?))
+}
+
+/// Context menu of the buffer view that overrides the font, palette and render flags of the shown buffer.
+pub fn show_buffer_menu(ui: &mut Ui, buffer_view: &mut BufferView) -> anyhow::Result<()> {
+    let font_name = buffer_view.get_buffer().get_font(0).map(|font| font.name.clone()).unwrap_or_default();
+    let mut selected_page = None;
+    ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-item-font"), |ui| {
+        egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+            for (page, name) in ANSI_FONT_NAMES.iter().enumerate().take(ANSI_FONTS) {
+                if ui.selectable_label(font_name == *name, *name).clicked() {
+                    selected_page = Some(page);
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+    if let Some(page) = selected_page {
+        set_font(buffer_view, BitFont::from_ansi_font_page(page)?);
+    }
+
+    if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-item-load-font")).clicked() {
+        ui.close_menu();
+        if let Some(path) = rfd::FileDialog::new().add_filter("Fonts", &FONT_EXTENSIONS).pick_file() {
+            set_font(buffer_view, load_font(&path)?);
+        }
+    }
+
+    ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-item-palette"), |ui| {
+        let palettes = [
+            (fl!(crate::LANGUAGE_LOADER, "palette-vga"), &VGA_PALETTE[..]),
+            (fl!(crate::LANGUAGE_LOADER, "palette-ega"), &EGA_PALETTE[..]),
+            (fl!(crate::LANGUAGE_LOADER, "palette-c64"), &C64_PALETTE[..]),
+            (fl!(crate::LANGUAGE_LOADER, "palette-amiga"), &AMIGA_PALETTE[..]),
+        ];
+        for (title, colors) in palettes {
+            if ui.button(title).clicked() {
+                set_palette(buffer_view, create_palette(colors));
+                ui.close_menu();
+            }
+        }
+    });
+    if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-item-load-palette")).clicked() {
+        ui.close_menu();
+        if let Some(path) = rfd::FileDialog::new().add_filter("Palettes", &["pal"]).pick_file() {
+            set_palette(buffer_view, load_palette(&path)?);
+        }
+    }
+    ui.separator();
+
+    let buf = buffer_view.get_buffer_mut();
+    let mut use_ice = buf.ice_mode == IceMode::Ice;
+    let mut use_letter_spacing = buf.use_letter_spacing();
+    let mut use_aspect_ratio = buf.use_aspect_ratio();
+    let mut changed = false;
+    if ui.checkbox(&mut use_ice, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-ice")).changed() {
+        buf.ice_mode = if use_ice { IceMode::Ice } else { IceMode::Blink };
+        changed = true;
+    }
+    if ui
+        .checkbox(&mut use_letter_spacing, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-letter-spacing"))
+        .changed()
+    {
+        buf.set_use_letter_spacing(use_letter_spacing);
+        changed = true;
+    }
+    if ui
+        .checkbox(&mut use_aspect_ratio, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-aspect-ratio"))
+        .changed()
+    {
+        buf.set_use_aspect_ratio(use_aspect_ratio);
+        changed = true;
+    }
+    if changed {
+        buffer_view.redraw_view();
+    }
+    Ok(())
+}
+
+fn set_font(buffer_view: &mut BufferView, font: BitFont) {
+    buffer_view.get_buffer_mut().set_font(0, font);
+    buffer_view.redraw_font();
+    buffer_view.redraw_view();
+}
+
+fn set_palette(buffer_view: &mut BufferView, palette: Palette) {
+    buffer_view.get_buffer_mut().palette = palette;
+    buffer_view.redraw_view();
+}
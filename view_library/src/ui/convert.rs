@@ -0,0 +1,160 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use icy_engine::{Buffer, SaveOptions, FORMATS};
+
+use super::loader;
+
+/// Options of a conversion, formats ignore the ones that don't apply to them.
+#[derive(Clone, Debug)]
+pub struct ConvertOptions {
+    pub save_sauce: bool,
+    pub compress: bool,
+    /// Maximum length of output lines, `None` doesn't wrap.
+    pub line_length: Option<usize>,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            save_sauce: true,
+            compress: true,
+            line_length: None,
+        }
+    }
+}
+
+impl ConvertOptions {
+    fn get_save_options(&self) -> SaveOptions {
+        let mut options = SaveOptions::new();
+        options.save_sauce = self.save_sauce;
+        options.compress = self.compress;
+        options.output_line_length = self.line_length;
+        options
+    }
+}
+
+/// Extension and name of every format buffers can be saved in.
+pub fn get_formats() -> Vec<(String, String)> {
+    FORMATS
+        .iter()
+        .map(|format| (format.get_file_extension().to_string(), format.get_name().to_string()))
+        .collect()
+}
+
+pub fn convert_buffer(buf: &Buffer, extension: &str, options: &ConvertOptions) -> anyhow::Result<Vec<u8>> {
+    let extension = extension.to_ascii_lowercase();
+    let Some(format) = FORMATS
+        .iter()
+        .find(|format| format.get_file_extension() == extension || format.get_alt_extensions().contains(&extension))
+    else {
+        let extensions: Vec<&str> = FORMATS.iter().map(|format| format.get_file_extension()).collect();
+        return Err(anyhow::anyhow!("Unknown format '{extension}', supported are: {}", extensions.join(", ")));
+    };
+    Ok(format.to_bytes(buf, &options.get_save_options())?)
+}
+
+pub fn convert_file(input: &Path, output: &Path, extension: &str, options: &ConvertOptions) -> anyhow::Result<()> {
+    check_output(input, output)?;
+    let data = fs::read(input)?;
+    let buf = Buffer::from_bytes(input, true, &data)?;
+    let bytes = convert_buffer(&buf, extension, options)?;
+    fs::write(output, bytes)?;
+    Ok(())
+}
+
+/// Converting to the same format would overwrite the source file.
+/// The paths are canonicalized, a relative path or a link to the input is the same file.
+pub fn check_output(input: &Path, output: &Path) -> anyhow::Result<()> {
+    if input == output {
+        return Err(anyhow::anyhow!("{} would overwrite itself", input.display()));
+    }
+    // an output that doesn't exist yet can't be the input, files inside archives can't be canonicalized
+    if let (Ok(input), Ok(output)) = (fs::canonicalize(input), fs::canonicalize(output)) {
+        if input == output {
+            return Err(anyhow::anyhow!("{} would overwrite itself", input.display()));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the outputs of a batch before anything is written, `jobs` are pairs of input and output file.
+/// Returns the reason for every job that has to be skipped: its output exists already and `overwrite` isn't set,
+/// or other inputs of the batch have the same output, like `foo.bin` and `foo.xb` converted to `foo.ans`.
+pub fn get_output_conflicts(jobs: &[(PathBuf, PathBuf)], overwrite: bool) -> Vec<Option<String>> {
+    let mut output_count: HashMap<&Path, usize> = HashMap::new();
+    for (_, output) in jobs {
+        *output_count.entry(output).or_default() += 1;
+    }
+    jobs.iter()
+        .map(|(_, output)| {
+            if output_count[output.as_path()] > 1 {
+                Some(format!("{} is the output of several files", output.display()))
+            } else if !overwrite && output.exists() {
+                Some(format!("{} already exists", output.display()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The input file name with the extension of the format, placed in `output_dir` or next to the input.
+pub fn get_output_path(input: &Path, output_dir: Option<&Path>, extension: &str) -> PathBuf {
+    let file_name = input.with_extension(extension);
+    match (output_dir, file_name.file_name()) {
+        (Some(dir), Some(file_name)) => dir.join(file_name),
+        _ => file_name,
+    }
+}
+
+/// Files in `dir` the engine can load, sub folders are skipped.
+pub fn get_convertible_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && loader::is_engine_format(&loader::get_extension(&path)) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_conflicts() {
+        let existing = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let jobs = vec![
+            (PathBuf::from("foo.bin"), PathBuf::from("out/foo.ans")),
+            (PathBuf::from("foo.xb"), PathBuf::from("out/foo.ans")),
+            (PathBuf::from("bar.xb"), PathBuf::from("out/bar.ans")),
+            (PathBuf::from("Cargo.xb"), existing),
+        ];
+        let conflicts = get_output_conflicts(&jobs, false);
+        assert!(conflicts[0].is_some());
+        assert!(conflicts[1].is_some());
+        assert!(conflicts[2].is_none());
+        assert!(conflicts[3].is_some());
+
+        // overwriting doesn't resolve outputs shared by several files
+        let conflicts = get_output_conflicts(&jobs, true);
+        assert!(conflicts[0].is_some());
+        assert!(conflicts[3].is_none());
+    }
+
+    #[test]
+    fn test_output_path() {
+        assert_eq!(get_output_path(Path::new("a/foo.bin"), None, "ans"), PathBuf::from("a/foo.ans"));
+        assert_eq!(
+            get_output_path(Path::new("a/foo.bin"), Some(Path::new("out")), "ans"),
+            PathBuf::from("out/foo.ans")
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use eframe::egui::{self, Layout};
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+
+use super::convert::{self, ConvertOptions};
+
+/// Line length the line wrap option starts with.
+const DEFAULT_LINE_LENGTH: usize = 80;
+
+pub struct ConvertDialog {
+    /// Extension and name of the formats.
+    formats: Vec<(String, String)>,
+    format: usize,
+    options: ConvertOptions,
+    line_length: usize,
+    /// Converts all files of the folder instead of the shown buffer.
+    convert_folder: bool,
+    can_convert_buffer: bool,
+}
+
+pub enum Message {
+    CloseDialog,
+    Convert {
+        extension: String,
+        options: ConvertOptions,
+        convert_folder: bool,
+    },
+}
+
+impl ConvertDialog {
+    pub fn new(can_convert_buffer: bool) -> Self {
+        let formats = convert::get_formats();
+        let format = formats.iter().position(|(extension, _)| extension == "ans").unwrap_or_default();
+        Self {
+            formats,
+            format,
+            options: ConvertOptions::default(),
+            line_length: DEFAULT_LINE_LENGTH,
+            convert_folder: !can_convert_buffer,
+            can_convert_buffer,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Message> {
+        let mut message = None;
+        let modal = Modal::new(ctx, "convert_modal");
+        modal.show(|ui| {
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "convert-dialog-title"));
+
+            modal.frame(ui, |ui: &mut egui::Ui| {
+                egui::Grid::new("convert_dialog_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "convert-dialog-format-label"));
+                    });
+                    let (extension, name) = &self.formats[self.format];
+                    egui::ComboBox::from_id_source("convert_format")
+                        .selected_text(format!("{name} (.{extension})"))
+                        .width(250.0)
+                        .show_ui(ui, |ui| {
+                            for (i, (extension, name)) in self.formats.iter().enumerate() {
+                                ui.selectable_value(&mut self.format, i, format!("{name} (.{extension})"));
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "convert-dialog-options-label"));
+                    });
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut self.options.save_sauce, fl!(crate::LANGUAGE_LOADER, "convert-dialog-save-sauce"));
+                        ui.checkbox(&mut self.options.compress, fl!(crate::LANGUAGE_LOADER, "convert-dialog-compress"));
+                        ui.horizontal(|ui| {
+                            let mut wrap = self.options.line_length.is_some();
+                            if ui.checkbox(&mut wrap, fl!(crate::LANGUAGE_LOADER, "convert-dialog-line-length")).changed() {
+                                self.options.line_length = wrap.then_some(self.line_length);
+                            }
+                            ui.add_enabled_ui(wrap, |ui| {
+                                if ui.add(egui::DragValue::new(&mut self.line_length).clamp_range(1..=4096)).changed() {
+                                    self.options.line_length = Some(self.line_length);
+                                }
+                            });
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "convert-dialog-source-label"));
+                    });
+                    ui.vertical(|ui| {
+                        ui.add_enabled_ui(self.can_convert_buffer, |ui| {
+                            ui.radio_value(&mut self.convert_folder, false, fl!(crate::LANGUAGE_LOADER, "convert-dialog-source-file"));
+                        });
+                        ui.radio_value(&mut self.convert_folder, true, fl!(crate::LANGUAGE_LOADER, "convert-dialog-source-folder"));
+                    });
+                    ui.end_row();
+                });
+            });
+
+            modal.buttons(ui, |ui| {
+                if modal.button(ui, fl!(crate::LANGUAGE_LOADER, "button-cancel")).clicked() {
+                    message = Some(Message::CloseDialog);
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "button-save")).clicked() {
+                    message = Some(Message::Convert {
+                        extension: self.formats[self.format].0.clone(),
+                        options: self.options.clone(),
+                        convert_folder: self.convert_folder,
+                    });
+                }
+            });
+        });
+        modal.open();
+
+        message
+    }
+}
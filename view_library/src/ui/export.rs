@@ -0,0 +1,149 @@
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use eframe::epaint::ColorImage;
+use icy_engine::Buffer;
+use icy_engine_gui::animations::Animator;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+    Delay, Frame, RgbaImage,
+};
+
+use super::{background_task::TaskProgress, keymap::Action, loader, playback};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Png,
+    Png2x,
+    Gif,
+    Clipboard,
+}
+
+impl ExportFormat {
+    pub fn get_action(&self) -> Action {
+        match self {
+            ExportFormat::Png => Action::ExportPng,
+            ExportFormat::Png2x => Action::ExportPng2x,
+            ExportFormat::Gif => Action::ExportGif,
+            ExportFormat::Clipboard => Action::CopyImage,
+        }
+    }
+}
+
+/// Frame rate of recorded playbacks.
+const PLAYBACK_FPS: u32 = 10;
+/// Recorded playbacks show a terminal screen that follows the caret.
+const PLAYBACK_SCREEN_LINES: i32 = 25;
+/// Ten minutes at the playback frame rate.
+const MAX_PLAYBACK_FRAMES: usize = 6000;
+
+pub fn scale_image(img: RgbaImage, scale: u32) -> RgbaImage {
+    if scale <= 1 {
+        return img;
+    }
+    image::imageops::resize(&img, img.width() * scale, img.height() * scale, FilterType::Nearest)
+}
+
+pub fn color_image_to_rgba(image: &ColorImage) -> anyhow::Result<RgbaImage> {
+    let [width, height] = image.size;
+    let pixels = image.pixels.iter().flat_map(|color| color.to_array()).collect();
+    RgbaImage::from_raw(width as u32, height as u32, pixels).ok_or_else(|| anyhow::anyhow!("Invalid image size {width}x{height}"))
+}
+
+pub fn save_png(img: &RgbaImage, path: &Path) -> anyhow::Result<()> {
+    img.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+pub fn copy_to_clipboard(img: &RgbaImage) -> anyhow::Result<()> {
+    let mut clipboard = arboard::Clipboard::new()?;
+    clipboard.set_image(arboard::ImageData {
+        width: img.width() as usize,
+        height: img.height() as usize,
+        bytes: Cow::Borrowed(img.as_raw()),
+    })?;
+    Ok(())
+}
+
+fn create_gif_encoder(path: &Path) -> anyhow::Result<GifEncoder<BufWriter<File>>> {
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(File::create(path)?), 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+    Ok(encoder)
+}
+
+/// Creates the GIF at `path`, a partly written file is removed if writing the frames fails or is cancelled.
+fn write_gif(path: &Path, write_frames: impl FnOnce(&mut GifEncoder<BufWriter<File>>) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    // the encoder is dropped before removing, the file can't be removed while it's open on Windows
+    let result = create_gif_encoder(path).and_then(|mut encoder| write_frames(&mut encoder));
+    if result.is_err() {
+        if let Err(err) = fs::remove_file(path) {
+            log::warn!("Error removing incomplete {}: {err}", path.display());
+        }
+    }
+    result
+}
+
+/// Writes all frames of a loaded icyanim file, the frame delays are in milliseconds.
+/// The animator is locked per frame, it keeps playing in the window meanwhile.
+pub fn save_animation_gif(anim: &Arc<Mutex<Animator>>, path: &Path, progress: &TaskProgress) -> anyhow::Result<()> {
+    let frame_count = {
+        let anim = anim.lock().unwrap();
+        if !anim.success() {
+            return Err(anyhow::anyhow!("The animation is still loading"));
+        }
+        anim.frames.len()
+    };
+    progress.set_total(frame_count);
+    write_gif(path, |encoder| {
+        for frame in 0..frame_count {
+            progress.check_cancelled()?;
+            let (img, delay) = {
+                let anim = anim.lock().unwrap();
+                let (buf, _, delay) = &anim.frames[frame];
+                (loader::render_buffer(buf)?, *delay)
+            };
+            encoder.encode_frame(Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(delay, 1)))?;
+            progress.advance();
+        }
+        Ok(())
+    })
+}
+
+/// Records the baud rate playback of a file, a baud rate of 0 results in a single frame.
+pub fn save_playback_gif(file: &Path, data: &[u8], baud_rate: u32, path: &Path, progress: &TaskProgress) -> anyhow::Result<()> {
+    let bytes_per_frame = if baud_rate == 0 {
+        data.len()
+    } else {
+        // 8N1: ten bits per byte
+        (baud_rate / 10 / PLAYBACK_FPS) as usize
+    };
+    progress.set_total(data.len().div_ceil(bytes_per_frame.max(1)).min(MAX_PLAYBACK_FRAMES));
+    let delay = Delay::from_numer_denom_ms(1000 / PLAYBACK_FPS, 1);
+    let mut first_line = 0;
+    let mut frames = 0;
+    write_gif(path, |encoder| {
+        playback::record(file, data, bytes_per_frame, |buf: &Buffer, caret| {
+            progress.check_cancelled()?;
+            let caret_line = caret.get_position().y;
+            if caret_line < first_line {
+                first_line = caret_line;
+            } else if caret_line >= first_line + PLAYBACK_SCREEN_LINES {
+                first_line = caret_line - PLAYBACK_SCREEN_LINES + 1;
+            }
+            let img = loader::render_lines(buf, first_line, PLAYBACK_SCREEN_LINES)?;
+            encoder.encode_frame(Frame::from_parts(img, 0, 0, delay))?;
+            frames += 1;
+            progress.advance();
+            if frames >= MAX_PLAYBACK_FRAMES {
+                return Err(anyhow::anyhow!("The playback is longer than ten minutes, choose a faster baud rate"));
+            }
+            Ok(())
+        })
+    })
+}
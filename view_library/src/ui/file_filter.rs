@@ -0,0 +1,230 @@
+use chrono::Datelike;
+
+use super::{file_view::FileEntry, loader};
+
+/// Extensions matched by the `type:` filter, other types are matched against the extension directly.
+const TYPE_EXTENSIONS: [(&str, &[&str]); 11] = [
+    ("ansi", &["ans", "ice"]),
+    ("ascii", &["asc", "txt", "nfo", "diz"]),
+    ("avatar", &["avt"]),
+    ("pcboard", &["pcb"]),
+    ("petscii", &["seq"]),
+    ("atascii", &["ata"]),
+    ("xbin", &["xb"]),
+    ("bin", &["bin"]),
+    ("tundra", &["tnd"]),
+    ("rip", &["rip"]),
+    ("animation", &["icyanim"]),
+];
+
+/// Parsed filter text, e.g. `blue author:x group:"y z" year:1995-1997 type:ansi`.
+///
+/// All conditions need to match. Words without a key match the file name or the SAUCE title, author and group.
+#[derive(Default, Debug, PartialEq)]
+pub struct FileFilter {
+    words: Vec<String>,
+    title: Vec<String>,
+    author: Vec<String>,
+    group: Vec<String>,
+    years: Option<(i32, i32)>,
+    types: Vec<String>,
+}
+
+impl FileFilter {
+    pub fn parse(text: &str) -> Self {
+        let mut filter = FileFilter::default();
+        for token in tokenize(text) {
+            let Some((key, value)) = token.split_once(':') else {
+                filter.words.push(token);
+                continue;
+            };
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "title" => filter.title.push(value.to_string()),
+                "author" => filter.author.push(value.to_string()),
+                "group" => filter.group.push(value.to_string()),
+                "type" => filter.types.extend(value.split(',').map(|t| t.to_string())),
+                "year" => {
+                    let (from, to) = value.split_once('-').unwrap_or((value, value));
+                    match (from.parse(), to.parse()) {
+                        (Ok(from), Ok(to)) => filter.years = Some((from, to)),
+                        _ => filter.words.push(token.clone()),
+                    }
+                }
+                _ => filter.words.push(token.clone()),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == FileFilter::default()
+    }
+
+    /// True if the filter needs SAUCE information to decide.
+    /// Plain words match the SAUCE fields only if they have been read already, the file name is enough for them.
+    pub fn needs_sauce(&self) -> bool {
+        !self.title.is_empty() || !self.author.is_empty() || !self.group.is_empty() || self.years.is_some()
+    }
+
+    pub fn matches(&self, entry: &FileEntry) -> bool {
+        self.matches_with_content(entry, None)
+    }
+
+    /// Like `matches`, words may also be found in the lower cased text `content` of the file.
+    pub fn matches_with_content(&self, entry: &FileEntry, content: Option<&str>) -> bool {
+        let file_name = entry.file_info.get_file_name().to_lowercase();
+        let title = entry.sauce.as_ref().map(|sauce| sauce.title().to_string().to_lowercase()).unwrap_or_default();
+        let author = entry.sauce.as_ref().map(|sauce| sauce.author().to_string().to_lowercase()).unwrap_or_default();
+        let group = entry.sauce.as_ref().map(|sauce| sauce.group().to_string().to_lowercase()).unwrap_or_default();
+
+        let words_match = self.words.iter().all(|word| {
+            file_name.contains(word)
+                || title.contains(word)
+                || author.contains(word)
+                || group.contains(word)
+                || content.map_or(false, |content| content.contains(word))
+        });
+        if !words_match
+            || !self.title.iter().all(|t| title.contains(t))
+            || !self.author.iter().all(|a| author.contains(a))
+            || !self.group.iter().all(|g| group.contains(g))
+        {
+            return false;
+        }
+
+        if let Some((from, to)) = self.years {
+            let year = entry.sauce.as_ref().and_then(|sauce| sauce.get_date().ok()).map(|date| date.year());
+            if !year.map_or(false, |year| (from..=to).contains(&year)) {
+                return false;
+            }
+        }
+
+        if !self.types.is_empty() {
+            if !entry.is_file() {
+                return false;
+            }
+            let ext = loader::get_extension(&entry.file_info.path);
+            if !self.types.iter().any(|t| matches_type(t, &ext)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn matches_type(file_type: &str, ext: &str) -> bool {
+    if file_type == "image" {
+        return loader::is_image_extension(ext);
+    }
+    match TYPE_EXTENSIONS.iter().find(|(name, _)| *name == file_type) {
+        Some((_, extensions)) => extensions.contains(&ext),
+        None => file_type == ext,
+    }
+}
+
+/// Splits the lower cased filter text at white space, double quotes group words (`author:"a b"`).
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    for ch in text.to_lowercase().chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            ch => token.push(ch),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::ui::file_view::FileInfo;
+
+    fn create_entry(path: &str) -> FileEntry {
+        FileEntry::from_file_info(FileInfo {
+            path: PathBuf::from(path),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("  Blue  author:\"A b\" "), vec!["blue", "author:a b"]);
+        assert_eq!(tokenize(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_parse_terms() {
+        let filter = FileFilter::parse("blue Title:x author:\"a b\" group:y year:1995-1997 type:ansi,rip");
+        assert_eq!(filter.words, vec!["blue"]);
+        assert_eq!(filter.title, vec!["x"]);
+        assert_eq!(filter.author, vec!["a b"]);
+        assert_eq!(filter.group, vec!["y"]);
+        assert_eq!(filter.years, Some((1995, 1997)));
+        assert_eq!(filter.types, vec!["ansi", "rip"]);
+    }
+
+    #[test]
+    fn test_parse_single_year() {
+        assert_eq!(FileFilter::parse("year:1996").years, Some((1996, 1996)));
+    }
+
+    #[test]
+    fn test_parse_invalid_terms() {
+        // unknown keys and invalid years are searched as words, empty values are ignored
+        let filter = FileFilter::parse("foo:bar year:abc author:");
+        assert_eq!(filter.words, vec!["foo:bar", "year:abc"]);
+        assert!(filter.author.is_empty());
+        assert!(FileFilter::parse("author: ").is_empty());
+    }
+
+    #[test]
+    fn test_needs_sauce() {
+        assert!(!FileFilter::parse("blue type:ansi").needs_sauce());
+        assert!(FileFilter::parse("title:x").needs_sauce());
+        assert!(FileFilter::parse("author:x").needs_sauce());
+        assert!(FileFilter::parse("group:x").needs_sauce());
+        assert!(FileFilter::parse("year:1995").needs_sauce());
+    }
+
+    #[test]
+    fn test_match_words() {
+        let entry = create_entry("/art/Blue_Moon.ANS");
+        assert!(FileFilter::parse("blue").matches(&entry));
+        assert!(FileFilter::parse("blue moon.ans").matches(&entry));
+        assert!(!FileFilter::parse("blue red").matches(&entry));
+        assert!(FileFilter::parse("red").matches_with_content(&entry, Some("a red moon")));
+    }
+
+    #[test]
+    fn test_match_types() {
+        assert!(FileFilter::parse("type:ansi").matches(&create_entry("a.ans")));
+        assert!(FileFilter::parse("type:ascii").matches(&create_entry("file_id.diz")));
+        assert!(!FileFilter::parse("type:ansi").matches(&create_entry("file_id.diz")));
+        assert!(FileFilter::parse("type:ansi,rip").matches(&create_entry("a.rip")));
+        assert!(FileFilter::parse("type:xyz").matches(&create_entry("a.xyz")));
+        assert!(FileFilter::parse("type:image").matches(&create_entry("a.png")));
+        assert!(!FileFilter::parse("type:image").matches(&create_entry("a.ans")));
+    }
+
+    #[test]
+    fn test_match_sauce_terms_without_sauce() {
+        let entry = create_entry("a.ans");
+        assert!(!FileFilter::parse("author:x").matches(&entry));
+        assert!(!FileFilter::parse("year:1995").matches(&entry));
+    }
+}
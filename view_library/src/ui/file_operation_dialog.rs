@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use eframe::egui::{self, TextEdit};
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+
+use super::file_operations::{self, FileOperation, FileOperationRequest};
+
+/// Number of file names the confirmation lists.
+const MAX_LISTED_FILES: usize = 10;
+
+pub enum Message {
+    CloseDialog,
+    Run(FileOperationRequest),
+}
+
+/// Confirms a file operation, renaming asks for the new name.
+pub struct FileOperationDialog {
+    operation: FileOperation,
+    files: Vec<PathBuf>,
+    destination: Option<PathBuf>,
+    new_name: String,
+    request_focus: bool,
+}
+
+impl FileOperationDialog {
+    pub fn new(operation: FileOperation, files: Vec<PathBuf>, destination: Option<PathBuf>) -> Self {
+        let new_name = files
+            .first()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self {
+            operation,
+            files,
+            destination,
+            new_name,
+            request_focus: true,
+        }
+    }
+
+    fn get_title(&self) -> String {
+        match self.operation {
+            FileOperation::Copy => fl!(crate::LANGUAGE_LOADER, "file-operation-copy-title"),
+            FileOperation::Move => fl!(crate::LANGUAGE_LOADER, "file-operation-move-title"),
+            FileOperation::Rename => fl!(crate::LANGUAGE_LOADER, "file-operation-rename-title"),
+            FileOperation::Trash => fl!(crate::LANGUAGE_LOADER, "file-operation-trash-title"),
+            FileOperation::Extract => fl!(crate::LANGUAGE_LOADER, "file-operation-extract-title"),
+        }
+    }
+
+    fn get_question(&self) -> String {
+        let count = self.files.len();
+        let destination = self.destination.as_ref().map(|path| path.display().to_string()).unwrap_or_default();
+        match self.operation {
+            FileOperation::Copy => fl!(crate::LANGUAGE_LOADER, "file-operation-copy-question", count = count, destination = destination),
+            FileOperation::Move => fl!(crate::LANGUAGE_LOADER, "file-operation-move-question", count = count, destination = destination),
+            FileOperation::Rename => fl!(crate::LANGUAGE_LOADER, "file-operation-rename-question"),
+            FileOperation::Trash => fl!(crate::LANGUAGE_LOADER, "file-operation-trash-question", count = count),
+            FileOperation::Extract => fl!(
+                crate::LANGUAGE_LOADER,
+                "file-operation-extract-question",
+                count = count,
+                destination = destination
+            ),
+        }
+    }
+
+    fn get_request(&self) -> Option<FileOperationRequest> {
+        let mut destination = self.destination.clone();
+        if self.operation == FileOperation::Rename {
+            let path = self.files.first()?;
+            let new_path = file_operations::get_renamed_path(path, &self.new_name).filter(|new_path| new_path != path)?;
+            destination = Some(new_path);
+        }
+        Some(FileOperationRequest {
+            operation: self.operation,
+            files: self.files.clone(),
+            destination,
+        })
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Message> {
+        let mut message = None;
+        let modal = Modal::new(ctx, "file_operation_modal");
+        modal.show(|ui| {
+            modal.title(ui, self.get_title());
+
+            modal.frame(ui, |ui: &mut egui::Ui| {
+                ui.label(self.get_question());
+                if self.operation == FileOperation::Rename {
+                    let response = ui.add(TextEdit::singleline(&mut self.new_name).desired_width(300.0));
+                    if self.request_focus {
+                        response.request_focus();
+                        self.request_focus = false;
+                    }
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        message = self.get_request().map(Message::Run);
+                    }
+                } else {
+                    ui.add_space(4.0);
+                    for path in self.files.iter().take(MAX_LISTED_FILES) {
+                        ui.strong(path.file_name().unwrap_or_default().to_string_lossy());
+                    }
+                    if self.files.len() > MAX_LISTED_FILES {
+                        ui.label(fl!(
+                            crate::LANGUAGE_LOADER,
+                            "file-operation-more-files",
+                            count = self.files.len() - MAX_LISTED_FILES
+                        ));
+                    }
+                }
+            });
+
+            modal.buttons(ui, |ui| {
+                if modal.button(ui, fl!(crate::LANGUAGE_LOADER, "button-cancel")).clicked() {
+                    message = Some(Message::CloseDialog);
+                }
+                let request = self.get_request();
+                if ui.add_enabled(request.is_some(), egui::Button::new(self.get_title())).clicked() {
+                    message = request.map(Message::Run);
+                }
+            });
+        });
+        modal.open();
+
+        message
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use i18n_embed_fl::fl;
+use walkdir::WalkDir;
+
+use super::{archive::ArchiveSource, keymap::Action};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileOperation {
+    Copy,
+    Move,
+    Rename,
+    Trash,
+    Extract,
+}
+
+impl FileOperation {
+    /// Extracting has no key of its own.
+    pub fn get_action(&self) -> Option<Action> {
+        match self {
+            FileOperation::Copy => Some(Action::CopyFiles),
+            FileOperation::Move => Some(Action::MoveFiles),
+            FileOperation::Rename => Some(Action::RenameFile),
+            FileOperation::Trash => Some(Action::TrashFiles),
+            FileOperation::Extract => None,
+        }
+    }
+
+    /// Copy, move and extract ask for a destination folder before they are confirmed.
+    pub fn needs_destination(&self) -> bool {
+        matches!(self, FileOperation::Copy | FileOperation::Move | FileOperation::Extract)
+    }
+}
+
+/// A confirmed operation, `destination` is the target folder or the new path of a renamed file.
+pub struct FileOperationRequest {
+    pub operation: FileOperation,
+    pub files: Vec<PathBuf>,
+    pub destination: Option<PathBuf>,
+}
+
+fn get_target(path: &Path, dest: &Path) -> anyhow::Result<PathBuf> {
+    let Some(file_name) = path.file_name() else {
+        anyhow::bail!(fl!(crate::LANGUAGE_LOADER, "file-error-no-file-name", path = path.display().to_string()));
+    };
+    let target = dest.join(file_name);
+    check_not_exists(&target)?;
+    if dest.starts_with(path) {
+        anyhow::bail!(fl!(crate::LANGUAGE_LOADER, "file-error-into-itself", path = path.display().to_string()));
+    }
+    Ok(target)
+}
+
+fn check_not_exists(target: &Path) -> anyhow::Result<()> {
+    if target.exists() {
+        anyhow::bail!(fl!(crate::LANGUAGE_LOADER, "file-error-exists", path = target.display().to_string()));
+    }
+    Ok(())
+}
+
+/// Checks all targets first, so a conflict doesn't leave the operation half done.
+fn get_targets(paths: &[PathBuf], dest: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    paths.iter().map(|path| get_target(path, dest)).collect()
+}
+
+fn copy_path(path: &Path, target: &Path) -> anyhow::Result<()> {
+    if !path.is_dir() {
+        fs::copy(path, target)?;
+        return Ok(());
+    }
+    for entry in WalkDir::new(path) {
+        let entry = entry?;
+        let entry_target = target.join(entry.path().strip_prefix(path)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&entry_target)?;
+        } else {
+            fs::copy(entry.path(), &entry_target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies files and folders into `dest`, returns the created paths.
+pub fn copy_files(paths: &[PathBuf], dest: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let targets = get_targets(paths, dest)?;
+    for (path, target) in paths.iter().zip(&targets) {
+        copy_path(path, target)?;
+    }
+    Ok(targets)
+}
+
+/// Moves files and folders into `dest`, other drives are copied to and the source is removed.
+pub fn move_files(paths: &[PathBuf], dest: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let targets = get_targets(paths, dest)?;
+    for (path, target) in paths.iter().zip(&targets) {
+        if fs::rename(path, target).is_err() {
+            copy_path(path, target)?;
+            if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            } else {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(targets)
+}
+
+pub fn rename_file(path: &Path, new_path: &Path) -> anyhow::Result<()> {
+    check_not_exists(new_path)?;
+    fs::rename(path, new_path)?;
+    Ok(())
+}
+
+/// New name of a renamed file, the name must not point to another folder.
+pub fn get_renamed_path(path: &Path, new_name: &str) -> Option<PathBuf> {
+    let new_name = new_name.trim();
+    let mut components = Path::new(new_name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Some(path.with_file_name(new_name)),
+        _ => None,
+    }
+}
+
+/// Replaces `path` with a file written by `write` to a temporary file next to it, the original stays
+/// untouched if writing fails. The temporary file is removed on errors.
+pub fn replace_file(path: &Path, write: impl FnOnce(&Path) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let Some(file_name) = path.file_name() else {
+        anyhow::bail!(fl!(crate::LANGUAGE_LOADER, "file-error-no-file-name", path = path.display().to_string()));
+    };
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = write(&tmp_path).and_then(|()| {
+        fs::set_permissions(&tmp_path, fs::metadata(path)?.permissions())?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    });
+    if result.is_err() && tmp_path.exists() {
+        if let Err(err) = fs::remove_file(&tmp_path) {
+            log::warn!("Error removing {}: {err}", tmp_path.display());
+        }
+    }
+    result
+}
+
+pub fn trash_files(paths: &[PathBuf]) -> anyhow::Result<()> {
+    trash::delete_all(paths)?;
+    Ok(())
+}
+
+/// Extracts entries of an archive folder into `dest`, folders are extracted with all files below them.
+/// Existing files aren't overwritten, all targets are checked before the first one is written.
+/// Returns the created top level paths.
+pub fn extract_entries(source: &ArchiveSource, folder: &Path, paths: &[PathBuf], dest: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut extracted = Vec::new();
+    for (path, dir, archive_entry) in source.list()? {
+        if dir || !paths.iter().any(|selected| path.starts_with(selected)) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(folder) else {
+            continue;
+        };
+        // archives may contain absolute paths or `..`, they must not write outside of `dest`
+        if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            log::warn!("Skipping archive entry {}", path.display());
+            continue;
+        }
+        let target = dest.join(relative);
+        check_not_exists(&target)?;
+        extracted.push((relative.to_path_buf(), target, archive_entry));
+    }
+
+    let mut created = Vec::new();
+    for (relative, target, archive_entry) in extracted {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, archive_entry.read_data()?.as_slice())?;
+
+        if let Some(first) = relative.components().next() {
+            let top_level = dest.join(first);
+            if !created.contains(&top_level) {
+                created.push(top_level);
+            }
+        }
+    }
+    Ok(created)
+}
+
+/// Extracts archives on disk into folders named after them.
+pub fn extract_archives(paths: &[PathBuf], dest: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut created = Vec::new();
+    for path in paths {
+        let Some(stem) = path.file_stem() else {
+            continue;
+        };
+        let target = dest.join(stem);
+        check_not_exists(&target)?;
+        extract_entries(&ArchiveSource::File(path.clone()), Path::new(""), &[PathBuf::new()], &target)?;
+        created.push(target);
+    }
+    Ok(created)
+}
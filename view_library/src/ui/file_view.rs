@@ -1,47 +1,100 @@
 use directories::UserDirs;
 use eframe::{
-    egui::{self, Image, Layout, RichText, Sense, TopBottomPanel, WidgetText},
-    epaint::{FontFamily, FontId, Rounding},
+    egui::{self, scroll_area::ScrollAreaOutput, Image, Layout, Modifiers, RichText, Sense, TopBottomPanel, WidgetText},
+    epaint::{Color32, FontFamily, FontId, Pos2, Rect, Rounding, Vec2},
 };
 use egui::{ScrollArea, TextEdit, Ui};
+use egui_extras::{Column, TableBuilder};
 use i18n_embed_fl::fl;
 use icy_sauce::SauceInformation;
 
 use std::{
+    cmp::Ordering,
+    collections::HashSet,
     env,
     fs::{self, File},
-    io::{Error, Read},
+    ops::Range,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 
-use super::options::{Options, ScrollSpeed};
+use super::{
+    archive::{is_archive, ArchiveEntry, ArchiveLevel, ArchiveSource},
+    export::ExportFormat,
+    file_filter::FileFilter,
+    file_operations::FileOperation,
+    folder_listing::FolderListing,
+    history::History,
+    keymap::{Action, Keymap},
+    loader,
+    options::{Options, ScaleMode, ScrollSpeed, SortColumn, ViewMode, MAX_ZOOM, MIN_ZOOM},
+    sauce_loader::SauceLoader,
+    search::{Search, SearchLocation},
+    thumbnails::{ThumbnailState, Thumbnails, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH},
+    watcher::FolderWatcher,
+};
+
+const GRID_SPACING: f32 = 4.0;
+pub(crate) const GRID_CELL_WIDTH: f32 = THUMBNAIL_WIDTH as f32 + 2.0 * GRID_SPACING;
 
 pub enum Message {
     Select(usize, bool),
+    Back,
+    Forward,
+    /// Shows a folder and selects the named entry in it.
+    Jump(PathBuf, Option<String>),
+    ShowQuickJump,
     Open(usize),
+    /// The selected file changed on disk.
+    Reload(usize),
     Cancel,
     Refresh,
     ParentFolder,
     ToggleAutoScroll,
     ShowSauce(usize),
     ShowHelpDialog,
+    ShowMonitorSettings,
+    Export(ExportFormat),
+    ShowConvertDialog,
+    FileOperation(FileOperation),
     ChangeScrollSpeed,
+    StartSearch,
+    ShowInFolder(usize),
+    ToggleSlideshow,
+    TogglePlayback,
 }
 
 #[derive(Clone)]
 pub struct FileEntry {
     pub file_info: FileInfo,
     pub file_data: Option<Vec<u8>>,
+    /// Set for files inside an archive, their data is only decompressed when needed.
+    pub archive_entry: Option<ArchiveEntry>,
     pub read_sauce: bool,
     pub sauce: Option<SauceInformation>,
 }
 
 impl FileEntry {
+    pub fn from_file_info(file_info: FileInfo) -> Self {
+        Self {
+            file_info,
+            file_data: None,
+            archive_entry: None,
+            read_sauce: false,
+            sauce: None,
+        }
+    }
+
     pub fn get_data<T>(&self, func: fn(&PathBuf, &[u8]) -> T) -> anyhow::Result<T> {
         if let Some(data) = &self.file_data {
             return Ok(func(&self.file_info.path, data));
         }
 
+        if let Some(archive_entry) = &self.archive_entry {
+            let data = archive_entry.read_data()?;
+            return Ok(func(&self.file_info.path, &data));
+        }
+
         let file = File::open(&self.file_info.path)?;
         let mmap = unsafe { memmap::MmapOptions::new().map(&file)? };
         Ok(func(&self.file_info.path, &mmap))
@@ -52,6 +105,9 @@ impl FileEntry {
         if let Some(data) = &self.file_data {
             let data = data.clone();
             Ok(func(&path.path, data))
+        } else if let Some(archive_entry) = &self.archive_entry {
+            let data = archive_entry.read_data()?;
+            Ok(func(&path.path, data.to_vec()))
         } else {
             let data = fs::read(&path.path)?;
             Ok(func(&path.path, data))
@@ -62,7 +118,17 @@ impl FileEntry {
         self.file_data.is_some() || !self.file_info.dir
     }
 
-    fn load_sauce(&mut self) {
+    /// Loads the data of archive entries, so the entry can be used after the archive has been closed.
+    pub fn load_data(&mut self) -> anyhow::Result<()> {
+        if self.file_data.is_none() {
+            if let Some(archive_entry) = &self.archive_entry {
+                self.file_data = Some(archive_entry.read_data()?.to_vec());
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn load_sauce(&mut self) {
         if self.read_sauce {
             return;
         }
@@ -77,14 +143,12 @@ impl FileEntry {
         self.file_info.dir
     }
 
-    fn is_dir_or_archive(&self) -> bool {
-        if let Some(ext) = self.file_info.path.extension() {
-            if ext.to_string_lossy().to_ascii_lowercase() == "zip" {
-                return true;
-            }
-        }
+    pub(crate) fn is_archive(&self) -> bool {
+        self.is_file() && is_archive(&self.file_info.path)
+    }
 
-        self.is_dir()
+    pub(crate) fn is_dir_or_archive(&self) -> bool {
+        self.is_archive() || self.is_dir()
     }
 
     pub(crate) fn get_sauce(&self) -> Option<SauceInformation> {
@@ -95,19 +159,65 @@ impl FileEntry {
     }
 }
 
+/// Entry that is selected once the folder listing has read it.
+enum PendingSelect {
+    Name(String),
+    /// The first entry, selected when the listing is finished so the order doesn't change anymore.
+    First,
+}
+
+/// A shown folder or archive folder, restored by going back and forward.
+#[derive(Clone)]
+pub struct Location {
+    path: PathBuf,
+    archive_stack: Vec<ArchiveLevel>,
+    /// File name of the selected entry.
+    selected_file: Option<String>,
+}
+
 pub struct FileView {
     /// Current opened path.
     path: PathBuf,
     /// Selected file path
     pub selected_file: Option<usize>,
+    /// Paths of the entries marked with ctrl/shift+click or space, file operations work on them.
+    pub marked: HashSet<PathBuf>,
+    /// Entry shift+click marks the range from.
+    mark_anchor: Option<PathBuf>,
     pub scroll_pos: Option<usize>,
     /// Files in directory.
     pub files: Vec<FileEntry>,
     pub upgrade_version: Option<String>,
+    /// Set by the main window while a slideshow is running.
+    pub slideshow_running: bool,
 
     pub options: super::options::Options,
+    /// Built from the `[keys]` table of the options at startup.
+    pub keymap: Keymap,
     pub filter: String,
+    /// Indices of the files matching `filter`, cached between frames.
+    filtered_indices: Vec<usize>,
+    /// Filter text `filtered_indices` was built for, `None` if the files changed since.
+    filtered_filter: Option<String>,
     pre_select_file: Option<String>,
+    /// Pre selected entry that hasn't been listed yet.
+    pending_select: Option<PendingSelect>,
+    /// Opened archives, the last one is shown. Archives inside archives are stacked on top of the archive on disk.
+    archive_stack: Vec<ArchiveLevel>,
+    pub history: History<Location>,
+    thumbnails: Thumbnails,
+    sauce_loader: SauceLoader,
+    watcher: FolderWatcher,
+    /// Running listing of the shown folder, `files` grows while it's set.
+    listing: Option<FolderListing>,
+
+    pub search_mode: bool,
+    search_text: String,
+    search_content: bool,
+    /// Running or finished recursive search, `files` holds its results while set.
+    search: Option<Search>,
+    /// Location of each search result, in the same order as `files`.
+    search_locations: Vec<SearchLocation>,
 }
 
 impl FileView {
@@ -127,7 +237,7 @@ impl FileView {
             path.pop();
         }
 
-        if path.is_file() && path.extension().unwrap_or_default().to_string_lossy().to_ascii_lowercase() != "zip" {
+        if path.is_file() && !is_archive(&path) {
             pre_select_file = Some(path.file_name().unwrap().to_string_lossy().to_string());
             path.pop();
         }
@@ -135,17 +245,41 @@ impl FileView {
         Self {
             path,
             selected_file: None,
+            marked: HashSet::new(),
+            mark_anchor: None,
             pre_select_file,
+            pending_select: None,
             scroll_pos: None,
             files: Vec::new(),
             filter: String::new(),
+            filtered_indices: Vec::new(),
+            filtered_filter: None,
+            keymap: Keymap::new(&options),
             options,
             upgrade_version: None,
+            slideshow_running: false,
+            archive_stack: Vec::new(),
+            history: History::default(),
+            thumbnails: Thumbnails::new(),
+            sauce_loader: SauceLoader::new(),
+            watcher: FolderWatcher::new(),
+            listing: None,
+            search_mode: false,
+            search_text: String::new(),
+            search_content: false,
+            search: None,
+            search_locations: Vec::new(),
         }
     }
 
     pub(crate) fn show_ui(&mut self, ui: &mut Ui, file_chooser: bool) -> Option<Message> {
         let mut command: Option<Message> = None;
+        // merged before the UI, so the indices of clicked entries match `files`
+        if self.listing.is_some() {
+            command = self.poll_listing(ui.ctx());
+        } else if let Some(changed_paths) = self.watcher.poll() {
+            command = self.apply_folder_changes(changed_paths);
+        }
 
         if file_chooser {
             TopBottomPanel::bottom("bottom_buttons").show_inside(ui, |ui| {
@@ -169,11 +303,18 @@ impl FileView {
                 TextEdit::singleline(&mut self.filter)
                     .hint_text(fl!(crate::LANGUAGE_LOADER, "filter-entries-hint-text"))
                     .desired_width(300.),
-            );
+            )
+            .on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-filter-syntax"));
             let response = ui.button("🗙").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-reset-filter-button"));
             if response.clicked() {
                 self.filter.clear();
             }
+            if self.sauce_loader.is_loading() && FileFilter::parse(&self.filter).needs_sauce() {
+                ui.spinner().on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-loading-sauce"));
+            }
+            if !self.marked.is_empty() {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "label-marked-files", count = self.marked.len()));
+            }
             if let Some(ver) = &self.upgrade_version {
                 ui.hyperlink_to(
                     fl!(crate::LANGUAGE_LOADER, "menu-upgrade_version", version = ver.clone()),
@@ -183,7 +324,15 @@ impl FileView {
         });
 
         ui.horizontal(|ui| {
-            match self.path.to_str() {
+            let mut display_path = self.get_display_path();
+            if !self.archive_stack.is_empty() {
+                if let Some(file) = self.selected_file.and_then(|idx| self.files.get(idx)) {
+                    if file.is_file() {
+                        display_path.push(file.file_info.get_file_name());
+                    }
+                }
+            }
+            match display_path.to_str() {
                 Some(path) => {
                     let mut path_edit = path.to_string();
                     ui.add(TextEdit::singleline(&mut path_edit).desired_width(f32::INFINITY));
@@ -193,18 +342,118 @@ impl FileView {
                 }
             }
 
-            ui.add_enabled_ui(self.path.parent().is_some(), |ui| {
+            ui.add_enabled_ui(self.history.can_go_back(), |ui| {
+                let tooltip = self.keymap.format_tooltip(ui.ctx(), fl!(crate::LANGUAGE_LOADER, "tooltip-back"), Action::Back);
+                let response = ui.button("⬅").on_hover_text(tooltip);
+                if response.clicked() {
+                    command = Some(Message::Back);
+                }
+            });
+            ui.add_enabled_ui(self.history.can_go_forward(), |ui| {
+                let tooltip = self
+                    .keymap
+                    .format_tooltip(ui.ctx(), fl!(crate::LANGUAGE_LOADER, "tooltip-forward"), Action::Forward);
+                let response = ui.button("➡").on_hover_text(tooltip);
+                if response.clicked() {
+                    command = Some(Message::Forward);
+                }
+            });
+
+            ui.add_enabled_ui(self.can_go_up(), |ui| {
                 let response = ui.button("⬆").on_hover_text("Parent Folder");
                 if response.clicked() {
                     command = Some(Message::ParentFolder);
                 }
             });
 
+            let path = self.get_path();
+            let is_bookmarked = self.options.is_bookmarked(&path);
+            ui.menu_button(if is_bookmarked { "★" } else { "☆" }, |ui| {
+                let title = if is_bookmarked {
+                    fl!(crate::LANGUAGE_LOADER, "menu-item-remove-bookmark")
+                } else {
+                    fl!(crate::LANGUAGE_LOADER, "menu-item-add-bookmark")
+                };
+                if ui.selectable_label(false, title).clicked() {
+                    self.options.toggle_bookmark(path.clone());
+                    ui.close_menu();
+                }
+                let button = egui::Button::new(fl!(crate::LANGUAGE_LOADER, "menu-item-quick-jump"))
+                    .shortcut_text(self.keymap.format_shortcut(ui.ctx(), Action::QuickJump));
+                if ui.add(button).clicked() {
+                    command = Some(Message::ShowQuickJump);
+                    ui.close_menu();
+                }
+
+                if !self.options.bookmarks.is_empty() {
+                    ui.separator();
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "menu-label-bookmarks")).small().weak());
+                    for bookmark in &self.options.bookmarks {
+                        if ui.selectable_label(*bookmark == path, bookmark.to_string_lossy()).clicked() {
+                            command = Some(Message::Jump(bookmark.clone(), None));
+                            ui.close_menu();
+                        }
+                    }
+                }
+                if !self.options.recent.is_empty() {
+                    ui.separator();
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "menu-label-recent")).small().weak());
+                    for location in &self.options.recent {
+                        let response = ui.selectable_label(false, location.path.to_string_lossy());
+                        let response = match &location.selected_file {
+                            Some(file) => response.on_hover_text(file),
+                            None => response,
+                        };
+                        if response.clicked() {
+                            command = Some(Message::Jump(location.path.clone(), location.selected_file.clone()));
+                            ui.close_menu();
+                        }
+                    }
+                }
+            })
+            .response
+            .on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-bookmarks"));
+
+            if self.search.is_some() {
+                let selected_result = self.selected_file.filter(|file| *file < self.search_locations.len());
+                ui.add_enabled_ui(selected_result.is_some(), |ui| {
+                    let response = ui.button("📂").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-show-in-folder"));
+                    if response.clicked() {
+                        if let Some(file) = selected_result {
+                            command = Some(Message::ShowInFolder(file));
+                        }
+                    }
+                });
+            }
+
+            let response = ui
+                .selectable_label(self.search_mode, "🔍")
+                .on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-recursive-search"));
+            if response.clicked() {
+                self.search_mode = !self.search_mode;
+                if !self.search_mode && self.search.is_some() {
+                    command = Some(Message::Refresh);
+                }
+            }
+
             let response = ui.button("⟲").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-refresh"));
             if response.clicked() {
                 command = Some(Message::Refresh);
             }
 
+            let (icon, tooltip) = match self.options.view_mode {
+                ViewMode::List => ("▦", fl!(crate::LANGUAGE_LOADER, "tooltip-grid-view")),
+                ViewMode::Grid => ("▤", fl!(crate::LANGUAGE_LOADER, "tooltip-table-view")),
+                ViewMode::Table => ("☰", fl!(crate::LANGUAGE_LOADER, "tooltip-list-view")),
+            };
+            if ui.button(icon).on_hover_text(tooltip).clicked() {
+                self.options.view_mode = match self.options.view_mode {
+                    ViewMode::List => ViewMode::Grid,
+                    ViewMode::Grid => ViewMode::Table,
+                    ViewMode::Table => ViewMode::List,
+                };
+            }
+
             ui.menu_button("…", |ui| {
                 let r = ui.hyperlink_to(
                     fl!(crate::LANGUAGE_LOADER, "menu-item-discuss"),
@@ -246,8 +495,123 @@ impl FileView {
                     command = Some(Message::ChangeScrollSpeed);
                     ui.close_menu();
                 }
+                ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-item-zoom"), |ui| {
+                    let options = &mut self.options;
+                    ui.radio_value(&mut options.scale_mode, ScaleMode::FitWidth, fl!(crate::LANGUAGE_LOADER, "zoom-fit-width"));
+                    ui.radio_value(&mut options.scale_mode, ScaleMode::FitPage, fl!(crate::LANGUAGE_LOADER, "zoom-fit-page"));
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut options.scale_mode, ScaleMode::Zoom, fl!(crate::LANGUAGE_LOADER, "zoom-fixed"));
+                        let mut percent = options.zoom * 100.0;
+                        let slider = egui::Slider::new(&mut percent, MIN_ZOOM * 100.0..=MAX_ZOOM * 100.0)
+                            .logarithmic(true)
+                            .suffix("%")
+                            .fixed_decimals(0);
+                        if ui.add(slider).changed() {
+                            options.set_zoom(percent / 100.0);
+                        }
+                    });
+                    ui.checkbox(&mut options.integer_scaling, fl!(crate::LANGUAGE_LOADER, "zoom-integer-scaling"));
+                });
+                let button =
+                    egui::Button::new(fl!(crate::LANGUAGE_LOADER, "menu-item-save-as")).shortcut_text(self.keymap.format_shortcut(ui.ctx(), Action::SaveAs));
+                if ui.add(button).clicked() {
+                    command = Some(Message::ShowConvertDialog);
+                    ui.close_menu();
+                }
+                ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-item-export"), |ui| {
+                    let items = [
+                        (fl!(crate::LANGUAGE_LOADER, "menu-item-export-png"), ExportFormat::Png),
+                        (fl!(crate::LANGUAGE_LOADER, "menu-item-export-png-2x"), ExportFormat::Png2x),
+                        (fl!(crate::LANGUAGE_LOADER, "menu-item-export-gif"), ExportFormat::Gif),
+                        (fl!(crate::LANGUAGE_LOADER, "menu-item-copy-image"), ExportFormat::Clipboard),
+                    ];
+                    for (title, format) in items {
+                        let button = egui::Button::new(title).shortcut_text(self.keymap.format_shortcut(ui.ctx(), format.get_action()));
+                        if ui.add(button).clicked() {
+                            command = Some(Message::Export(format));
+                            ui.close_menu();
+                        }
+                    }
+                });
+                if ui.selectable_label(false, fl!(crate::LANGUAGE_LOADER, "menu-item-monitor-settings")).clicked() {
+                    command = Some(Message::ShowMonitorSettings);
+                    ui.close_menu();
+                }
+                ui.separator();
+                let title = if self.slideshow_running {
+                    fl!(crate::LANGUAGE_LOADER, "menu-item-stop-slideshow")
+                } else {
+                    fl!(crate::LANGUAGE_LOADER, "menu-item-start-slideshow")
+                };
+                if ui.selectable_label(false, title).clicked() {
+                    command = Some(Message::ToggleSlideshow);
+                    ui.close_menu();
+                }
+                if ui.selectable_label(false, fl!(crate::LANGUAGE_LOADER, "menu-item-playback")).clicked() {
+                    command = Some(Message::TogglePlayback);
+                    ui.close_menu();
+                }
+                ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-item-slideshow-settings"), |ui| {
+                    let slideshow = &mut self.options.slideshow;
+                    ui.add(egui::Slider::new(&mut slideshow.delay, 1..=300).text(fl!(crate::LANGUAGE_LOADER, "slideshow-delay")));
+                    ui.checkbox(&mut slideshow.wait_for_end, fl!(crate::LANGUAGE_LOADER, "slideshow-wait-for-end"));
+                    ui.checkbox(&mut slideshow.shuffle, fl!(crate::LANGUAGE_LOADER, "slideshow-shuffle"));
+                    ui.checkbox(&mut slideshow.repeat, fl!(crate::LANGUAGE_LOADER, "slideshow-repeat"));
+                    ui.checkbox(&mut slideshow.recursive, fl!(crate::LANGUAGE_LOADER, "slideshow-recursive"));
+                });
             });
         });
+        if self.search_mode {
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.search_text)
+                        .hint_text(fl!(crate::LANGUAGE_LOADER, "search-hint-text"))
+                        .desired_width(300.),
+                );
+                let can_search = !self.search_text.trim().is_empty();
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && can_search {
+                    command = Some(Message::StartSearch);
+                }
+                ui.checkbox(&mut self.search_content, fl!(crate::LANGUAGE_LOADER, "search-content-checkbox"));
+
+                match &self.search {
+                    Some(search) if !search.is_finished() => {
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "button-stop")).clicked() {
+                            search.cancel();
+                        }
+                        ui.spinner();
+                    }
+                    _ => {
+                        if ui
+                            .add_enabled(can_search, egui::Button::new(fl!(crate::LANGUAGE_LOADER, "button-search")))
+                            .clicked()
+                        {
+                            command = Some(Message::StartSearch);
+                        }
+                    }
+                }
+                if let Some(search) = &self.search {
+                    ui.label(fl!(
+                        crate::LANGUAGE_LOADER,
+                        "label-search-status",
+                        found = self.files.len(),
+                        scanned = search.get_scanned()
+                    ));
+                }
+            });
+        }
+        self.poll_search(ui.ctx());
+
+        if let Some(listing) = &self.listing {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(fl!(crate::LANGUAGE_LOADER, "label-listing-status", count = self.files.len()));
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "button-stop")).clicked() {
+                    listing.cancel();
+                }
+            });
+        }
+
         if self.selected_file.is_none() && !self.files.is_empty() {
             //  command = Some(Command::Select(0));
         }
@@ -257,284 +621,945 @@ impl FileView {
         let strong_color = ui.style().visuals.strong_text_color();
         let text_color = ui.style().visuals.text_color();
 
-        let filter = self.filter.to_lowercase();
-        let filtered_entries = self.files.iter_mut().enumerate().filter(|(_, p)| {
-            if filter.is_empty() {
-                return true;
-            }
-            if let Some(sauce) = &p.sauce {
-                if sauce.title().to_string().to_lowercase().contains(&filter)
-                /*    || sauce
-                    .group
-                    .to_string()
-                    .to_lowercase()
-                    .contains(&filter)
-                || sauce
-                    .author
-                    .to_string()
-                    .to_lowercase()
-                    .contains(&filter)*/
-                {
-                    return true;
-                }
+        if self.sauce_loader.update(&mut self.files) {
+            if self.options.sort_column.needs_sauce() {
+                self.sort_files();
+            } else if FileFilter::parse(&self.filter).needs_sauce() {
+                self.invalidate_filter();
             }
-            p.file_info.path.to_string_lossy().to_lowercase().contains(&filter)
-        });
+        }
+        if self.sauce_loader.is_loading() {
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        }
 
-        let mut indices = Vec::new();
-        let area_res = area.show(ui, |ui| {
-            for (real_idx, entry) in filtered_entries {
-                let (id, rect) = ui.allocate_space([ui.available_width(), row_height].into());
-
-                indices.push(real_idx);
-                let is_selected = Some(real_idx) == self.selected_file;
-                let text_color = if is_selected { strong_color } else { text_color };
-                let mut response = ui.interact(rect, id, Sense::click());
-                if response.hovered() {
-                    ui.painter()
-                        .rect_filled(rect.expand(1.0), Rounding::same(4.0), ui.style().visuals.widgets.active.bg_fill);
-                } else if is_selected {
-                    ui.painter()
-                        .rect_filled(rect.expand(1.0), Rounding::same(4.0), ui.style().visuals.extreme_bg_color);
-                }
-
-                let label = if !ui.is_rect_visible(rect) {
-                    get_file_name(&entry.file_info.path).to_string()
-                } else {
-                    match entry.is_dir_or_archive() {
-                        true => "🗀 ",
-                        false => "🗋 ",
+        self.update_filtered_indices();
+        // taken for the frame, the views borrow `self` mutably
+        let indices = std::mem::take(&mut self.filtered_indices);
+        self.thumbnails.update(ui.ctx());
+        let area_rect = match self.options.view_mode {
+            ViewMode::Grid => self.show_grid(ui, &indices, &mut command).inner_rect,
+            ViewMode::Table => {
+                let rect = ui.available_rect_before_wrap();
+                self.show_table(ui, &indices, &mut command);
+                rect
+            }
+            ViewMode::List => {
+                area.show_rows(ui, row_height, indices.len(), |ui, row_range| {
+                    if let Some(row) = self.scroll_pos.take().and_then(|file| indices.iter().position(|i| *i == file)) {
+                        scroll_to_row(ui, &row_range, row, row_height);
                     }
-                    .to_string()
-                        + get_file_name(&entry.file_info.path)
-                };
-
-                let font_id = FontId::new(14.0, FontFamily::Proportional);
-                let text: WidgetText = label.into();
-                let galley = text.into_galley(ui, Some(false), f32::INFINITY, font_id);
-                ui.painter()
-                    .galley_with_override_text_color(egui::Align2::LEFT_TOP.align_size_within_rect(galley.size(), rect).min, galley, text_color);
-                if response.hovered() {
-                    entry.load_sauce();
-                    if let Some(sauce) = &entry.sauce {
-                        response = response.on_hover_ui(|ui| {
-                            egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 2.0]).show(ui, |ui| {
-                                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                    ui.label(fl!(crate::LANGUAGE_LOADER, "heading-title"));
-                                });
-                                ui.strong(sauce.title().to_string());
-                                ui.end_row();
-                                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                    ui.label(fl!(crate::LANGUAGE_LOADER, "heading-author"));
-                                });
-                                ui.strong(sauce.author().to_string());
-                                ui.end_row();
-                                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                    ui.label(fl!(crate::LANGUAGE_LOADER, "heading-group"));
-                                });
-                                ui.strong(sauce.group().to_string());
-                                ui.end_row();
-                                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                    ui.label(fl!(crate::LANGUAGE_LOADER, "heading-screen-mode"));
-                                });
-                                let mut flags: String = String::new();
-                                if let Ok(caps) = sauce.get_character_capabilities() {
-                                    if caps.use_ice {
-                                        flags.push_str("ICE");
-                                    }
-
-                                    if caps.use_letter_spacing {
-                                        if !flags.is_empty() {
-                                            flags.push(',');
-                                        }
-                                        flags.push_str("9px");
-                                    }
+                    for &real_idx in &indices[row_range] {
+                        let name = self.get_entry_name(real_idx);
+                        let entry = &self.files[real_idx];
+                        let (id, rect) = ui.allocate_space([ui.available_width(), row_height].into());
+
+                        let is_selected = Some(real_idx) == self.selected_file || self.marked.contains(&entry.file_info.path);
+                        let text_color = if is_selected { strong_color } else { text_color };
+                        let mut response = ui.interact(rect, id, Sense::click());
+                        if response.hovered() {
+                            ui.painter()
+                                .rect_filled(rect.expand(1.0), Rounding::same(4.0), ui.style().visuals.widgets.active.bg_fill);
+                        } else if is_selected {
+                            ui.painter()
+                                .rect_filled(rect.expand(1.0), Rounding::same(4.0), ui.style().visuals.extreme_bg_color);
+                        }
 
-                                    if caps.use_aspect_ratio {
-                                        if !flags.is_empty() {
-                                            flags.push(',');
+                        let icon = if entry.is_dir_or_archive() { "🗀 " } else { "🗋 " };
+                        let label = icon.to_string() + &name;
+
+                        let font_id = FontId::new(14.0, FontFamily::Proportional);
+                        let text: WidgetText = label.into();
+                        let galley = text.into_galley(ui, Some(false), f32::INFINITY, font_id);
+                        ui.painter().galley_with_override_text_color(
+                            egui::Align2::LEFT_TOP.align_size_within_rect(galley.size(), rect).min,
+                            galley,
+                            text_color,
+                        );
+                        if response.hovered() {
+                            // read by the sauce loader, hovering must not block on the file
+                            if let Some(sauce) = &entry.sauce {
+                                response = response.on_hover_ui(|ui| {
+                                    egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 2.0]).show(ui, |ui| {
+                                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                                            ui.label(fl!(crate::LANGUAGE_LOADER, "heading-title"));
+                                        });
+                                        ui.strong(sauce.title().to_string());
+                                        ui.end_row();
+                                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                                            ui.label(fl!(crate::LANGUAGE_LOADER, "heading-author"));
+                                        });
+                                        ui.strong(sauce.author().to_string());
+                                        ui.end_row();
+                                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                                            ui.label(fl!(crate::LANGUAGE_LOADER, "heading-group"));
+                                        });
+                                        ui.strong(sauce.group().to_string());
+                                        ui.end_row();
+                                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                                            ui.label(fl!(crate::LANGUAGE_LOADER, "heading-screen-mode"));
+                                        });
+                                        let mut flags: String = String::new();
+                                        if let Ok(caps) = sauce.get_character_capabilities() {
+                                            if caps.use_ice {
+                                                flags.push_str("ICE");
+                                            }
+
+                                            if caps.use_letter_spacing {
+                                                if !flags.is_empty() {
+                                                    flags.push(',');
+                                                }
+                                                flags.push_str("9px");
+                                            }
+
+                                            if caps.use_aspect_ratio {
+                                                if !flags.is_empty() {
+                                                    flags.push(',');
+                                                }
+                                                flags.push_str("AR");
+                                            }
+
+                                            if flags.is_empty() {
+                                                ui.strong(RichText::new(format!("{}x{}", caps.width, caps.height)));
+                                            } else {
+                                                ui.strong(RichText::new(format!("{}x{} ({})", caps.width, caps.height, flags)));
+                                            }
                                         }
-                                        flags.push_str("AR");
-                                    }
-
-                                    if flags.is_empty() {
-                                        ui.strong(RichText::new(format!("{}x{}", caps.width, caps.height)));
-                                    } else {
-                                        ui.strong(RichText::new(format!("{}x{} ({})", caps.width, caps.height, flags)));
-                                    }
-                                }
-                                ui.end_row();
-                            });
-                        });
-                    }
-                }
+                                        ui.end_row();
+                                    });
+                                });
+                            }
+                        }
 
-                if response.clicked() {
-                    command = Some(Message::Select(real_idx, false));
-                }
+                        if response.clicked() {
+                            command = self.click_entry(real_idx, ui.input(|i| i.modifiers), &indices);
+                        }
+                        if response.secondary_clicked() && !self.is_operation_file(real_idx) {
+                            command = self.click_entry(real_idx, Modifiers::NONE, &indices);
+                        }
+                        response.context_menu(|ui| self.show_operation_menu(ui, &mut command));
 
-                if response.double_clicked() {
-                    command = Some(Message::Open(real_idx));
-                }
+                        if response.double_clicked() {
+                            command = Some(Message::Open(real_idx));
+                        }
+                    }
+                })
+                .inner_rect
             }
-        });
+        };
 
         if ui.is_enabled() {
-            if ui.input(|i| i.key_pressed(egui::Key::PageUp) && i.modifiers.alt) {
+            if self.keymap.pressed(ui.ctx(), Action::ParentFolder) {
                 command = Some(Message::ParentFolder);
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::F1)) {
+            if self.keymap.pressed(ui.ctx(), Action::Back) || ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Extra1)) {
+                command = Some(Message::Back);
+            }
+
+            if self.keymap.pressed(ui.ctx(), Action::Forward) || ui.input(|i| i.pointer.button_clicked(egui::PointerButton::Extra2)) {
+                command = Some(Message::Forward);
+            }
+
+            if self.keymap.consume(ui.ctx(), Action::QuickJump) {
+                command = Some(Message::ShowQuickJump);
+            }
+
+            if self.keymap.pressed(ui.ctx(), Action::ShowHelp) {
                 command = Some(Message::ShowHelpDialog);
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::F2)) {
+            if self.keymap.pressed(ui.ctx(), Action::ToggleAutoScroll) {
                 command = Some(Message::ToggleAutoScroll);
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::F3)) {
+            if self.keymap.pressed(ui.ctx(), Action::ChangeScrollSpeed) {
                 command = Some(Message::ChangeScrollSpeed);
             }
 
+            if self.keymap.pressed(ui.ctx(), Action::ToggleSlideshow) {
+                command = Some(Message::ToggleSlideshow);
+            }
+
+            if self.keymap.pressed(ui.ctx(), Action::TogglePlayback) {
+                command = Some(Message::TogglePlayback);
+            }
+
+            if self.keymap.consume(ui.ctx(), Action::SaveAs) {
+                command = Some(Message::ShowConvertDialog);
+            }
+
+            for format in [ExportFormat::Png, ExportFormat::Png2x, ExportFormat::Gif, ExportFormat::Clipboard] {
+                if self.keymap.consume(ui.ctx(), format.get_action()) {
+                    command = Some(Message::Export(format));
+                }
+            }
+
+            // space and delete belong to text fields while one has focus
+            if !ui.ctx().wants_keyboard_input() {
+                if self.keymap.pressed(ui.ctx(), Action::ToggleMark) {
+                    self.toggle_mark();
+                }
+                for operation in [FileOperation::Copy, FileOperation::Move, FileOperation::Rename, FileOperation::Trash] {
+                    if operation.get_action().is_some_and(|action| self.keymap.consume(ui.ctx(), action)) {
+                        command = Some(Message::FileOperation(operation));
+                    }
+                }
+            }
+
             if let Some(s) = self.selected_file {
-                if ui.input(|i| i.key_pressed(egui::Key::F4)) {
+                if self.keymap.pressed(ui.ctx(), Action::ShowSauce) {
                     command = Some(Message::ShowSauce(s));
                 }
                 let found = indices.iter().position(|i| *i == s);
                 if let Some(idx) = found {
-                    if ui.input(|i| i.key_pressed(egui::Key::ArrowUp) && i.modifiers.is_none()) && idx > 0 {
-                        command = Some(Message::Select(indices[idx - 1], false));
+                    // in the grid up/down move by a whole row
+                    let row_len = if self.options.view_mode == ViewMode::Grid {
+                        get_grid_columns(area_rect.width())
+                    } else {
+                        1
+                    };
+                    if self.keymap.pressed(ui.ctx(), Action::SelectPrevious) && idx >= row_len {
+                        command = Some(Message::Select(indices[idx - row_len], false));
+                    }
+
+                    if self.keymap.pressed(ui.ctx(), Action::SelectNext) && idx + row_len < indices.len() {
+                        command = Some(Message::Select(indices[idx + row_len], false));
                     }
 
-                    if ui.input(|i| i.key_pressed(egui::Key::ArrowDown) && i.modifiers.is_none()) && idx + 1 < indices.len() {
-                        command = Some(Message::Select(indices[idx + 1], false));
+                    if self.options.view_mode == ViewMode::Grid {
+                        if self.keymap.pressed(ui.ctx(), Action::SelectLeft) && idx > 0 {
+                            command = Some(Message::Select(indices[idx - 1], false));
+                        }
+
+                        if self.keymap.pressed(ui.ctx(), Action::SelectRight) && idx + 1 < indices.len() {
+                            command = Some(Message::Select(indices[idx + 1], false));
+                        }
                     }
 
-                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if self.keymap.pressed(ui.ctx(), Action::Open) && command.is_none() {
                         command = Some(Message::Open(s));
                     }
 
                     if !self.files.is_empty() {
-                        if ui.input(|i: &egui::InputState| i.key_pressed(egui::Key::Home) && i.modifiers.is_none() && !indices.is_empty()) {
+                        if self.keymap.pressed(ui.ctx(), Action::SelectFirst) && !indices.is_empty() {
                             command = Some(Message::Select(indices[0], false));
                         }
 
-                        if ui.input(|i| i.key_pressed(egui::Key::End) && i.modifiers.is_none()) && !indices.is_empty() {
+                        if self.keymap.pressed(ui.ctx(), Action::SelectLast) && !indices.is_empty() {
                             command = Some(Message::Select(indices[indices.len() - 1], false));
                         }
 
-                        if ui.input(|i| i.key_pressed(egui::Key::PageUp) && i.modifiers.is_none()) && !indices.is_empty() {
-                            let page_size = (area_res.inner_rect.height() / row_height) as usize;
+                        if self.keymap.pressed(ui.ctx(), Action::SelectPageUp) && !indices.is_empty() {
+                            let page_size = (area_rect.height() / row_height) as usize;
                             command = Some(Message::Select(indices[idx.saturating_sub(page_size)], false));
                         }
 
-                        if ui.input(|i| i.key_pressed(egui::Key::PageDown) && i.modifiers.is_none()) && !indices.is_empty() {
-                            let page_size = (area_res.inner_rect.height() / row_height) as usize;
+                        if self.keymap.pressed(ui.ctx(), Action::SelectPageDown) && !indices.is_empty() {
+                            let page_size = (area_rect.height() / row_height) as usize;
                             command = Some(Message::Select(indices[(idx.saturating_add(page_size)).min(indices.len() - 1)], false));
                         }
                     }
                 }
             } else if !self.files.is_empty() {
-                if ui.input(|i| {
-                    i.key_pressed(egui::Key::ArrowUp)
-                        || i.key_pressed(egui::Key::ArrowDown)
-                        || i.key_pressed(egui::Key::PageUp)
-                        || i.key_pressed(egui::Key::PageDown)
-                }) {
+                if self.keymap.pressed(ui.ctx(), Action::SelectPrevious)
+                    || self.keymap.pressed(ui.ctx(), Action::SelectNext)
+                    || self.keymap.pressed(ui.ctx(), Action::SelectPageUp)
+                    || self.keymap.pressed(ui.ctx(), Action::SelectPageDown)
+                {
                     command = Some(Message::Select(0, false));
                 }
 
-                if ui.input(|i| i.key_pressed(egui::Key::Home)) {
+                if self.keymap.pressed(ui.ctx(), Action::SelectFirst) {
                     command = Some(Message::Select(0, false));
                 }
 
-                if ui.input(|i| i.key_pressed(egui::Key::End)) {
+                if self.keymap.pressed(ui.ctx(), Action::SelectLast) {
                     command = Some(Message::Select(self.files.len().saturating_sub(1), false));
                 }
             }
         }
 
+        self.filtered_indices = indices;
         command
     }
 
+    /// Selects a clicked entry, ctrl+click toggles its mark and shift+click marks the range from the last clicked entry.
+    fn click_entry(&mut self, file: usize, modifiers: Modifiers, indices: &[usize]) -> Option<Message> {
+        let path = self.files[file].file_info.path.clone();
+        if modifiers.shift {
+            let anchor = self
+                .mark_anchor
+                .as_ref()
+                .and_then(|anchor| self.files.iter().position(|entry| entry.file_info.path == *anchor))
+                .or(self.selected_file)
+                .and_then(|anchor| indices.iter().position(|i| *i == anchor));
+            if let (Some(start), Some(end)) = (anchor, indices.iter().position(|i| *i == file)) {
+                if !modifiers.command {
+                    self.marked.clear();
+                }
+                for &i in &indices[start.min(end)..=start.max(end)] {
+                    self.marked.insert(self.files[i].file_info.path.clone());
+                }
+            }
+        } else if modifiers.command {
+            // the selected entry is the first one of a new selection
+            if self.marked.is_empty() {
+                if let Some(selected) = self
+                    .selected_file
+                    .filter(|selected| *selected != file)
+                    .and_then(|selected| self.files.get(selected))
+                {
+                    self.marked.insert(selected.file_info.path.clone());
+                }
+            }
+            if !self.marked.remove(&path) {
+                self.marked.insert(path.clone());
+            }
+            self.mark_anchor = Some(path);
+        } else {
+            self.marked.clear();
+            self.mark_anchor = Some(path);
+        }
+        Some(Message::Select(file, false))
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(entry) = self.selected_file.and_then(|file| self.files.get(file)) else {
+            return;
+        };
+        let path = entry.file_info.path.clone();
+        if !self.marked.remove(&path) {
+            self.marked.insert(path.clone());
+        }
+        self.mark_anchor = Some(path);
+    }
+
+    /// Entries the file operations work on, the marked ones or else the selected one.
+    pub fn get_operation_files(&self) -> Vec<&FileEntry> {
+        if self.marked.is_empty() {
+            return self.selected_file.and_then(|file| self.files.get(file)).into_iter().collect();
+        }
+        self.files.iter().filter(|entry| self.marked.contains(&entry.file_info.path)).collect()
+    }
+
+    fn is_operation_file(&self, file: usize) -> bool {
+        if self.marked.is_empty() {
+            self.selected_file == Some(file)
+        } else {
+            self.marked.contains(&self.files[file].file_info.path)
+        }
+    }
+
+    /// Archive folder extract works on, `None` while files on disk or search results are shown.
+    pub fn get_archive_level(&self) -> Option<&ArchiveLevel> {
+        self.archive_stack.last().filter(|_| self.search.is_none())
+    }
+
+    pub fn can_run(&self, operation: FileOperation) -> bool {
+        let files = self.get_operation_files();
+        if files.is_empty() {
+            return false;
+        }
+        let on_disk = self.archive_stack.is_empty() && files.iter().all(|entry| entry.archive_entry.is_none() && entry.file_data.is_none());
+        match operation {
+            FileOperation::Copy | FileOperation::Move | FileOperation::Trash => on_disk,
+            FileOperation::Rename => on_disk && files.len() == 1,
+            FileOperation::Extract => self.get_archive_level().is_some() || (on_disk && files.iter().all(|entry| entry.is_archive())),
+        }
+    }
+
+    fn show_operation_menu(&self, ui: &mut Ui, command: &mut Option<Message>) {
+        let items = [
+            (fl!(crate::LANGUAGE_LOADER, "menu-item-copy-to"), FileOperation::Copy),
+            (fl!(crate::LANGUAGE_LOADER, "menu-item-move-to"), FileOperation::Move),
+            (fl!(crate::LANGUAGE_LOADER, "menu-item-rename"), FileOperation::Rename),
+            (fl!(crate::LANGUAGE_LOADER, "menu-item-trash"), FileOperation::Trash),
+            (fl!(crate::LANGUAGE_LOADER, "menu-item-extract-to"), FileOperation::Extract),
+        ];
+        for (title, operation) in items {
+            let mut button = egui::Button::new(title);
+            if let Some(action) = operation.get_action() {
+                button = button.shortcut_text(self.keymap.format_shortcut(ui.ctx(), action));
+            }
+            if ui.add_enabled(self.can_run(operation), button).clicked() {
+                *command = Some(Message::FileOperation(operation));
+                ui.close_menu();
+            }
+        }
+    }
+
+    /// Updates the list after a file operation without reading the folder again.
+    pub fn update_after_operation(&mut self, changed_paths: Vec<PathBuf>) -> Option<Message> {
+        self.marked.clear();
+        self.mark_anchor = None;
+        if self.search.is_none() {
+            return self.apply_folder_changes(changed_paths);
+        }
+
+        // search results come from many folders, the ones that are gone are dropped
+        let selected_path = self
+            .selected_file
+            .and_then(|file| self.files.get(file))
+            .map(|entry| entry.file_info.path.clone());
+        let keep: Vec<bool> = self
+            .files
+            .iter()
+            .map(|entry| entry.archive_entry.is_some() || entry.file_info.path.exists())
+            .collect();
+        let mut keep_files = keep.iter();
+        self.files.retain(|_| *keep_files.next().unwrap());
+        let mut keep_locations = keep.iter();
+        self.search_locations.retain(|_| *keep_locations.next().unwrap());
+        self.selected_file = selected_path.and_then(|path| self.files.iter().position(|entry| entry.file_info.path == path));
+        self.invalidate_filter();
+        None
+    }
+
+    /// Marks the cached filter result as outdated, called whenever `files` changes.
+    pub(crate) fn invalidate_filter(&mut self) {
+        self.filtered_filter = None;
+    }
+
+    /// Filters the files again if they or the filter text changed since the last frame.
+    fn update_filtered_indices(&mut self) {
+        if self.filtered_filter.as_deref() == Some(self.filter.as_str()) {
+            return;
+        }
+        let filter = FileFilter::parse(&self.filter);
+        self.filtered_indices = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| filter.is_empty() || filter.matches(entry))
+            .map(|(i, _)| i)
+            .collect();
+        self.filtered_filter = Some(self.filter.clone());
+    }
+
+    fn show_grid(&mut self, ui: &mut Ui, indices: &[usize], command: &mut Option<Message>) -> ScrollAreaOutput<()> {
+        let columns = get_grid_columns(ui.available_width());
+        let rows = indices.len().div_ceil(columns);
+        let label_height = ui.text_style_height(&egui::TextStyle::Body);
+        let cell_size = Vec2::new(GRID_CELL_WIDTH, THUMBNAIL_HEIGHT as f32 + label_height + 2.0 * GRID_SPACING);
+
+        let output = ScrollArea::vertical().show_rows(ui, cell_size.y, rows, |ui, row_range| {
+            if let Some(row) = self.scroll_pos.take().and_then(|file| indices.iter().position(|i| *i == file)) {
+                scroll_to_row(ui, &row_range, row / columns, cell_size.y);
+            }
+            for row in row_range {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for &real_idx in indices.iter().skip(row * columns).take(columns) {
+                        let (id, rect) = ui.allocate_space(cell_size);
+                        let response = ui.interact(rect, id, Sense::click());
+                        let is_selected = Some(real_idx) == self.selected_file || self.marked.contains(&self.files[real_idx].file_info.path);
+                        if response.hovered() {
+                            ui.painter()
+                                .rect_filled(rect.shrink(1.0), Rounding::same(4.0), ui.style().visuals.widgets.active.bg_fill);
+                        } else if is_selected {
+                            ui.painter()
+                                .rect_filled(rect.shrink(1.0), Rounding::same(4.0), ui.style().visuals.extreme_bg_color);
+                        }
+
+                        let image_rect = Rect::from_min_size(
+                            rect.min + Vec2::new((rect.width() - THUMBNAIL_WIDTH as f32) / 2.0, GRID_SPACING),
+                            Vec2::new(THUMBNAIL_WIDTH as f32, THUMBNAIL_HEIGHT as f32),
+                        );
+                        let entry = &self.files[real_idx];
+                        let has_preview = entry.is_file() && !entry.is_archive() && loader::has_preview(&loader::get_extension(&entry.file_info.path));
+                        if has_preview {
+                            match self.thumbnails.get(entry) {
+                                ThumbnailState::Loaded(texture) => {
+                                    let size = texture.size_vec2();
+                                    let rect = Rect::from_min_size(image_rect.min, size.min(image_rect.size()));
+                                    ui.painter()
+                                        .image(texture.id(), rect, Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)), Color32::WHITE);
+                                }
+                                ThumbnailState::Loading => {
+                                    ui.put(Rect::from_center_size(image_rect.center(), Vec2::splat(24.0)), egui::Spinner::new());
+                                }
+                                ThumbnailState::Failed => {
+                                    paint_grid_icon(ui, image_rect, "🗋");
+                                }
+                            }
+                        } else {
+                            paint_grid_icon(ui, image_rect, if entry.is_dir_or_archive() { "🗀" } else { "🗋" });
+                        }
+
+                        let text_color = if is_selected {
+                            ui.style().visuals.strong_text_color()
+                        } else {
+                            ui.style().visuals.text_color()
+                        };
+                        let label_rect = Rect::from_min_size(
+                            Pos2::new(rect.min.x + GRID_SPACING, image_rect.max.y + GRID_SPACING),
+                            Vec2::new(rect.width() - 2.0 * GRID_SPACING, label_height),
+                        );
+                        let text: WidgetText = self.get_entry_name(real_idx).into();
+                        let galley = text.into_galley(ui, Some(false), label_rect.width(), egui::TextStyle::Body);
+                        ui.painter().with_clip_rect(label_rect).galley_with_override_text_color(
+                            egui::Align2::CENTER_TOP.align_size_within_rect(galley.size(), label_rect).min,
+                            galley,
+                            text_color,
+                        );
+
+                        if response.clicked() {
+                            *command = self.click_entry(real_idx, ui.input(|i| i.modifiers), indices);
+                        }
+                        if response.secondary_clicked() && !self.is_operation_file(real_idx) {
+                            *command = self.click_entry(real_idx, Modifiers::NONE, indices);
+                        }
+                        response.context_menu(|ui| self.show_operation_menu(ui, command));
+
+                        if response.double_clicked() {
+                            *command = Some(Message::Open(real_idx));
+                        }
+                    }
+                });
+            }
+        });
+
+        if self.thumbnails.is_loading() {
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        }
+        output
+    }
+
+    fn show_table(&mut self, ui: &mut Ui, indices: &[usize], command: &mut Option<Message>) {
+        let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(Sense::click())
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(180.0).at_least(60.0).clip(true))
+            .columns(Column::initial(80.0).at_least(40.0).clip(true), TABLE_COLUMNS.len() - 1);
+        if let Some(scroll_pos) = self.scroll_pos.take() {
+            if let Some(row) = indices.iter().position(|i| *i == scroll_pos) {
+                table = table.scroll_to_row(row, None);
+            }
+        }
+
+        let mut sort_column = None;
+        table
+            .header(row_height, |mut header| {
+                for column in TABLE_COLUMNS {
+                    header.col(|ui| {
+                        let mut title = get_column_title(column);
+                        if self.options.sort_column == column {
+                            title.push_str(if self.options.sort_descending { " ⏷" } else { " ⏶" });
+                        }
+                        if ui.add(egui::Label::new(RichText::new(title).strong()).sense(Sense::click())).clicked() {
+                            sort_column = Some(column);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, indices.len(), |mut row| {
+                    let real_idx = indices[row.index()];
+                    row.set_selected(Some(real_idx) == self.selected_file || self.marked.contains(&self.files[real_idx].file_info.path));
+                    let name = self.get_entry_name(real_idx);
+                    let entry = &self.files[real_idx];
+                    for column in TABLE_COLUMNS {
+                        row.col(|ui| {
+                            if column == SortColumn::Name {
+                                let icon = if entry.is_dir_or_archive() { "🗀 " } else { "🗋 " };
+                                ui.label(RichText::new(icon.to_string() + &name).strong());
+                            } else {
+                                ui.label(get_column_text(entry, column));
+                            }
+                        });
+                    }
+
+                    let response = row.response();
+                    if response.clicked() {
+                        *command = self.click_entry(real_idx, response.ctx.input(|i| i.modifiers), indices);
+                    }
+                    if response.secondary_clicked() && !self.is_operation_file(real_idx) {
+                        *command = self.click_entry(real_idx, Modifiers::NONE, indices);
+                    }
+                    response.context_menu(|ui| self.show_operation_menu(ui, command));
+                    if response.double_clicked() {
+                        *command = Some(Message::Open(real_idx));
+                    }
+                });
+            });
+
+        if let Some(column) = sort_column {
+            if self.options.sort_column == column {
+                self.options.sort_descending = !self.options.sort_descending;
+            } else {
+                self.options.sort_column = column;
+                self.options.sort_descending = false;
+            }
+            self.sort_files();
+            self.scroll_pos = self.selected_file;
+        }
+    }
+
+    /// Sorts the files by the sort column of the options, folders are kept before files.
+    pub fn sort_files(&mut self) {
+        let column = self.options.sort_column;
+        let descending = self.options.sort_descending;
+
+        // lowercasing for every comparison would allocate O(n log n) strings
+        let keys: Vec<String> = self.files.iter().map(|entry| get_sort_text(entry, column)).collect();
+        let mut order: Vec<usize> = (0..self.files.len()).collect();
+        order.sort_by(|a, b| {
+            let (a_key, b_key) = (&keys[*a], &keys[*b]);
+            let (a, b) = (&self.files[*a], &self.files[*b]);
+            b.is_dir().cmp(&a.is_dir()).then_with(|| {
+                let order = compare_entries(a, a_key, b, b_key, column).then_with(|| a.file_info.path.file_name().cmp(&b.file_info.path.file_name()));
+                if descending {
+                    order.reverse()
+                } else {
+                    order
+                }
+            })
+        });
+
+        reorder(&mut self.files, &order);
+        self.invalidate_filter();
+        if !self.search_locations.is_empty() {
+            reorder(&mut self.search_locations, &order);
+        }
+        if let Some(selected) = self.selected_file {
+            self.selected_file = order.iter().position(|i| *i == selected);
+        }
+    }
+
+    /// File name of an entry, search results are shown with their path relative to the search root.
+    fn get_entry_name(&self, file: usize) -> String {
+        match self.search_locations.get(file) {
+            Some(location) => location.display_name.clone(),
+            None => get_file_name(&self.files[file].file_info.path).to_string(),
+        }
+    }
+
+    /// Starts a recursive search in the current folder or archive, the results replace the files.
+    pub fn start_search(&mut self) {
+        self.listing = None;
+        self.marked.clear();
+        self.mark_anchor = None;
+        self.files.clear();
+        self.search_locations.clear();
+        self.selected_file = None;
+        self.thumbnails.clear();
+        self.sauce_loader.load(&self.files);
+        self.invalidate_filter();
+        self.search = Some(Search::start(self.path.clone(), &self.search_text, self.search_content));
+    }
+
+    fn poll_search(&mut self, ctx: &egui::Context) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        for result in search.poll() {
+            self.files.push(result.entry);
+            self.search_locations.push(result.location);
+            self.filtered_filter = None;
+        }
+        if !search.is_finished() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// Leaves the search and shows the folder containing the search result `file`.
+    pub fn show_in_folder(&mut self, file: usize) -> Option<Message> {
+        let location = self.search_locations.get(file)?.clone();
+        self.pre_select_file = Some(self.files[file].file_info.get_file_name().to_string());
+        self.path = location.path;
+        self.archive_stack.clear();
+        if let Some(folder) = location.archive_folder {
+            let mut level = ArchiveLevel::new(ArchiveSource::File(self.path.clone()), None);
+            level.folder = folder;
+            self.archive_stack.push(level);
+        }
+        self.refresh()
+    }
+
     pub fn get_path(&self) -> PathBuf {
         self.path.clone()
     }
 
+    /// Path of the shown folder, including the path inside opened archives (e.g. `pack.zip/sub`).
+    pub fn get_display_path(&self) -> PathBuf {
+        let mut result = self.path.clone();
+        for level in &self.archive_stack {
+            if let Some(entry_path) = &level.entry_path {
+                result.push(entry_path);
+            }
+        }
+        if let Some(level) = self.archive_stack.last() {
+            result.push(&level.folder);
+        }
+        result
+    }
+
+    pub fn get_location(&self) -> Location {
+        Location {
+            path: self.path.clone(),
+            archive_stack: self.archive_stack.clone(),
+            selected_file: self
+                .selected_file
+                .and_then(|file| self.files.get(file))
+                .map(|entry| entry.file_info.get_file_name().to_string()),
+        }
+    }
+
+    /// Adds the shown location to the history, called before navigating somewhere else.
+    pub fn remember_location(&mut self) {
+        let location = self.get_location();
+        self.history.push(location);
+    }
+
+    /// Shows a location of the history again, the returned message selects the entry that was selected there.
+    pub fn restore_location(&mut self, location: Location) -> Option<Message> {
+        self.path = location.path;
+        self.archive_stack = location.archive_stack;
+        self.pre_select_file = location.selected_file;
+        self.refresh()
+    }
+
+    /// Stores the shown folder in the recent locations, the selected entry is only kept outside of archive folders.
+    pub fn remember_recent(&mut self) {
+        let location = self.get_location();
+        let in_archive_root = match &location.archive_stack[..] {
+            [] => true,
+            [level] => level.folder.as_os_str().is_empty(),
+            _ => false,
+        };
+        let selected_file = if in_archive_root { location.selected_file } else { None };
+        self.options.add_recent(location.path, selected_file);
+    }
+
+    /// Shows a bookmark or recent location, the returned message selects `selected_file` if it's still there.
+    pub fn jump_to(&mut self, path: PathBuf, selected_file: Option<String>) -> Option<Message> {
+        self.pre_select_file = selected_file;
+        self.set_path(path)
+    }
+
     pub fn set_path(&mut self, path: impl Into<PathBuf>) -> Option<Message> {
         self.path = path.into();
+        self.archive_stack.clear();
         self.refresh()
     }
 
-    pub fn refresh(&mut self) -> Option<Message> {
-        self.files.clear();
+    pub fn can_go_up(&self) -> bool {
+        !self.archive_stack.is_empty() || self.path.parent().is_some()
+    }
 
-        if self.path.is_file() {
-            match fs::File::open(&self.path) {
-                Ok(file) => match zip::ZipArchive::new(file) {
-                    Ok(mut archive) => {
-                        for i in 0..archive.len() {
-                            match archive.by_index(i) {
-                                Ok(mut file) => {
-                                    let mut data = Vec::new();
-                                    file.read_to_end(&mut data).unwrap_or_default();
-
-                                    let entry = FileEntry {
-                                        file_info: FileInfo {
-                                            path: file.enclosed_name().unwrap_or(Path::new("unknown")).to_path_buf(),
-                                            dir: file.is_dir(),
-                                        },
-                                        file_data: Some(data),
-                                        read_sauce: false,
-                                        sauce: None,
-                                    };
-                                    self.files.push(entry);
-                                }
-                                Err(err) => {
-                                    log::error!("Error reading zip file: {}", err);
-                                }
-                            }
-                        }
+    /// Moves one level up, leaving the current archive folder or archive first.
+    pub fn parent_folder(&mut self) -> bool {
+        if let Some(level) = self.archive_stack.last_mut() {
+            if level.folder.pop() {
+                self.refresh();
+                return true;
+            }
+            self.archive_stack.pop();
+            if !self.archive_stack.is_empty() {
+                self.refresh();
+                return true;
+            }
+        }
+
+        let mut path = self.path.clone();
+        if path.pop() {
+            self.set_path(path);
+            return true;
+        }
+        false
+    }
+
+    /// Enters the folder or archive at `file`, returns false if the entry is a regular file.
+    pub fn open(&mut self, file: usize) -> bool {
+        let Some(entry) = self.files.get(file) else {
+            return false;
+        };
+
+        if entry.is_dir() {
+            let path = entry.file_info.path.clone();
+            if let Some(level) = self.archive_stack.last_mut() {
+                level.folder = path;
+                self.refresh();
+            } else {
+                self.set_path(path);
+            }
+            return true;
+        }
+
+        if entry.is_archive() {
+            if let Some(archive_entry) = &entry.archive_entry {
+                match archive_entry.read_data() {
+                    Ok(data) => {
+                        let entry_path = entry.file_info.path.clone();
+                        let source = ArchiveSource::Memory {
+                            name: entry_path.clone(),
+                            data,
+                        };
+                        self.archive_stack.push(ArchiveLevel::new(source, Some(entry_path)));
+                        self.refresh();
                     }
                     Err(err) => {
-                        log::error!("Error reading zip archive: {}", err);
+                        log::error!("Error reading archive {}: {err}", entry.file_info.path.display());
+                        return false;
                     }
-                },
-                Err(err) => {
-                    log::error!("Failed to open zip file: {}", err);
                 }
+            } else {
+                let path = entry.file_info.path.clone();
+                self.set_path(path);
             }
-        } else {
-            let folders = read_folder(&self.path);
-            match folders {
-                Ok(folders) => {
-                    self.files = folders
-                        .iter()
-                        .map(|f| FileEntry {
-                            file_info: f.clone(),
-                            read_sauce: false,
-                            sauce: None,
-                            file_data: None,
-                        })
-                        .collect();
+            return true;
+        }
+        false
+    }
+
+    /// Merges the changes of the watched folder into `files`, the selection stays on the same file.
+    /// Returns a reload of the selected file if it has been modified.
+    fn apply_folder_changes(&mut self, changed_paths: Vec<PathBuf>) -> Option<Message> {
+        // search results and archive folders aren't watched
+        if self.search.is_some() || !self.archive_stack.is_empty() {
+            return None;
+        }
+        let selected_path = self
+            .selected_file
+            .and_then(|file| self.files.get(file))
+            .map(|entry| entry.file_info.path.clone());
+        let mut selected_modified = false;
+
+        for path in changed_paths {
+            if path.parent() != Some(self.path.as_path()) {
+                continue;
+            }
+            self.thumbnails.invalidate(&path);
+            let index = self.files.iter().position(|entry| entry.file_info.path == path);
+            let info = FileInfo::new(path);
+            let shown = info.path.exists() && is_shown(&info);
+            if shown && selected_path.as_ref() == Some(&info.path) {
+                selected_modified = true;
+            }
+            match index {
+                Some(i) if shown => self.files[i] = FileEntry::from_file_info(info),
+                Some(i) => {
+                    self.files.remove(i);
+                }
+                None if shown => self.files.push(FileEntry::from_file_info(info)),
+                None => {}
+            }
+        }
+
+        self.selected_file = None;
+        self.sort_files();
+        self.sauce_loader.load(&self.files);
+        self.selected_file = selected_path.and_then(|path| self.files.iter().position(|entry| entry.file_info.path == path));
+        match self.selected_file {
+            Some(file) if selected_modified => Some(Message::Reload(file)),
+            _ => None,
+        }
+    }
+
+    pub fn refresh(&mut self) -> Option<Message> {
+        self.files.clear();
+        self.thumbnails.clear();
+        self.search = None;
+        self.search_locations.clear();
+        self.listing = None;
+        self.marked.clear();
+        self.mark_anchor = None;
+
+        if self.archive_stack.is_empty() && self.path.is_file() {
+            self.archive_stack.push(ArchiveLevel::new(ArchiveSource::File(self.path.clone()), None));
+        }
+
+        if let Some(level) = self.archive_stack.last() {
+            match level.source.list() {
+                Ok(entries) => {
+                    self.files = list_archive_folder(entries, &level.folder);
                 }
                 Err(err) => {
-                    log::error!("Failed to read folder: {}", err);
+                    log::error!("Error reading archive: {}", err);
                 }
             }
+        } else {
+            #[cfg(windows)]
+            {
+                self.files = get_drives()
+                    .into_iter()
+                    .map(|drive| {
+                        FileEntry::from_file_info(FileInfo {
+                            path: drive,
+                            dir: true,
+                            ..Default::default()
+                        })
+                    })
+                    .collect();
+            }
+            self.listing = Some(FolderListing::start(self.path.clone()));
         }
         self.selected_file = None;
+        self.sort_files();
+        self.sauce_loader.load(&self.files);
+        let watch_path = self.archive_stack.is_empty().then(|| self.path.clone());
+        self.watcher.watch(watch_path.as_deref());
 
-        if let Some(file) = &self.pre_select_file {
-            for (i, entry) in self.files.iter().enumerate() {
-                if let Some(file_name) = entry.file_info.path.file_name() {
-                    if file_name.to_string_lossy() == *file {
-                        return Message::Select(i, false).into();
-                    }
+        self.pending_select = self.pre_select_file.clone().map(PendingSelect::Name);
+        self.select_pending_file()
+    }
+
+    /// Selects the pre selected file once it has been listed, unless another file has been selected in the meantime.
+    fn select_pending_file(&mut self) -> Option<Message> {
+        if self.selected_file.is_some() {
+            self.pending_select = None;
+        }
+        let file = match self.pending_select.as_ref()? {
+            PendingSelect::Name(file_name) => self
+                .files
+                .iter()
+                .position(|entry| entry.file_info.path.file_name().is_some_and(|name| name.to_string_lossy() == *file_name))?,
+            PendingSelect::First => {
+                if self.listing.is_some() || self.files.is_empty() {
+                    return None;
                 }
+                0
             }
+        };
+        self.pending_select = None;
+        Some(Message::Select(file, false))
+    }
+
+    /// Selects the first entry of the shown folder, once it has been listed.
+    pub fn select_first(&mut self) -> Option<Message> {
+        self.pending_select = Some(PendingSelect::First);
+        self.select_pending_file()
+    }
+
+    /// Adds the entries the folder listing has read so far.
+    fn poll_listing(&mut self, ctx: &egui::Context) -> Option<Message> {
+        let listing = self.listing.as_ref()?;
+        // checked first, entries sent before the thread finished are still in the channel
+        let finished = listing.is_finished();
+        let entries = listing.poll();
+        if !entries.is_empty() {
+            self.files.extend(entries.into_iter().map(FileEntry::from_file_info));
+            self.invalidate_filter();
         }
-        None
+        // sorted once at the end, sorting the growing list on every poll is too slow for large folders
+        if finished {
+            self.listing = None;
+            self.sort_files();
+            if self.selected_file.is_some() {
+                self.scroll_pos = self.selected_file;
+            }
+            self.sauce_loader.load(&self.files);
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        let command = self.select_pending_file();
+        if finished {
+            self.pending_select = None;
+        }
+        command
     }
 }
 
@@ -546,6 +1571,153 @@ fn is_drive_root(path: &Path) -> bool {
         .map_or(false, |ch| ch.is_ascii_uppercase())
 }
 
+/// Reorders `items` so that the new item `i` is the old item `order[i]`.
+fn reorder<T>(items: &mut Vec<T>, order: &[usize]) {
+    let mut old: Vec<Option<T>> = items.drain(..).map(Some).collect();
+    items.extend(order.iter().filter_map(|i| old[*i].take()));
+}
+
+fn get_grid_columns(width: f32) -> usize {
+    ((width / GRID_CELL_WIDTH) as usize).max(1)
+}
+
+const TABLE_COLUMNS: [SortColumn; 8] = [
+    SortColumn::Name,
+    SortColumn::Size,
+    SortColumn::Modified,
+    SortColumn::Title,
+    SortColumn::Author,
+    SortColumn::Group,
+    SortColumn::Date,
+    SortColumn::Dimensions,
+];
+
+fn get_column_title(column: SortColumn) -> String {
+    match column {
+        SortColumn::Name => fl!(crate::LANGUAGE_LOADER, "heading-name"),
+        SortColumn::Size => fl!(crate::LANGUAGE_LOADER, "heading-size"),
+        SortColumn::Modified => fl!(crate::LANGUAGE_LOADER, "heading-modified"),
+        SortColumn::Title => fl!(crate::LANGUAGE_LOADER, "heading-title"),
+        SortColumn::Author => fl!(crate::LANGUAGE_LOADER, "heading-author"),
+        SortColumn::Group => fl!(crate::LANGUAGE_LOADER, "heading-group"),
+        SortColumn::Date => fl!(crate::LANGUAGE_LOADER, "heading-date"),
+        SortColumn::Dimensions => fl!(crate::LANGUAGE_LOADER, "heading-dimensions"),
+    }
+}
+
+fn get_column_text(entry: &FileEntry, column: SortColumn) -> String {
+    match column {
+        SortColumn::Name => {
+            match entry.is_dir_or_archive() {
+                true => "🗀 ",
+                false => "🗋 ",
+            }
+            .to_string()
+                + get_file_name(&entry.file_info.path)
+        }
+        SortColumn::Size => {
+            if entry.is_dir() {
+                String::new()
+            } else {
+                format_size(entry.file_info.size)
+            }
+        }
+        SortColumn::Modified => entry
+            .file_info
+            .modified
+            .map(|modified| chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default(),
+        SortColumn::Title => entry
+            .sauce
+            .as_ref()
+            .map(|sauce| sauce.title().to_string().trim_end().to_string())
+            .unwrap_or_default(),
+        SortColumn::Author => entry
+            .sauce
+            .as_ref()
+            .map(|sauce| sauce.author().to_string().trim_end().to_string())
+            .unwrap_or_default(),
+        SortColumn::Group => entry
+            .sauce
+            .as_ref()
+            .map(|sauce| sauce.group().to_string().trim_end().to_string())
+            .unwrap_or_default(),
+        SortColumn::Date => entry
+            .sauce
+            .as_ref()
+            .and_then(|sauce| sauce.get_date().ok())
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        SortColumn::Dimensions => get_dimensions(entry).map(|(width, height)| format!("{width}x{height}")).unwrap_or_default(),
+    }
+}
+
+fn get_dimensions(entry: &FileEntry) -> Option<(usize, usize)> {
+    let caps = entry.sauce.as_ref()?.get_character_capabilities().ok()?;
+    Some((caps.width as usize, caps.height as usize))
+}
+
+/// Lowercase text an entry is sorted by, empty for the columns that aren't sorted by text.
+fn get_sort_text(entry: &FileEntry, column: SortColumn) -> String {
+    match column {
+        SortColumn::Name => entry.file_info.get_file_name().to_lowercase(),
+        SortColumn::Size | SortColumn::Modified | SortColumn::Dimensions => String::new(),
+        _ => get_column_text(entry, column).to_lowercase(),
+    }
+}
+
+/// Compares two entries in ascending order, entries without a value come last. `a_key` and `b_key` are their [`get_sort_text`].
+fn compare_entries(a: &FileEntry, a_key: &str, b: &FileEntry, b_key: &str, column: SortColumn) -> Ordering {
+    match column {
+        SortColumn::Name => a_key.cmp(b_key),
+        SortColumn::Size => a.file_info.size.cmp(&b.file_info.size),
+        SortColumn::Modified => compare_optional(a.file_info.modified, b.file_info.modified),
+        SortColumn::Dimensions => compare_optional(get_dimensions(a), get_dimensions(b)),
+        _ => compare_optional(Some(a_key).filter(|a| !a.is_empty()), Some(b_key).filter(|b| !b.is_empty())),
+    }
+}
+
+fn compare_optional<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if size < 1024 {
+        return format!("{size} B");
+    }
+    let mut size = size as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Scrolls a list shown with `show_rows` to `row`, which may lie outside of the laid out `row_range`.
+fn scroll_to_row(ui: &Ui, row_range: &Range<usize>, row: usize, row_height: f32) {
+    let row_height_with_spacing = row_height + ui.spacing().item_spacing.y;
+    let top = ui.max_rect().top() + (row as f32 - row_range.start as f32) * row_height_with_spacing;
+    let rect = Rect::from_min_size(Pos2::new(ui.max_rect().left(), top), Vec2::new(ui.max_rect().width(), row_height));
+    ui.scroll_to_rect(rect, None);
+}
+
+fn paint_grid_icon(ui: &Ui, rect: Rect, icon: &str) {
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        icon,
+        FontId::new(48.0, FontFamily::Proportional),
+        ui.style().visuals.weak_text_color(),
+    );
+}
+
 fn get_file_name(path: &Path) -> &str {
     #[cfg(windows)]
     if path.is_dir() && is_drive_root(path) {
@@ -574,59 +1746,83 @@ fn get_drives() -> Vec<PathBuf> {
     drive_names
 }
 
-fn read_folder(path: &Path) -> Result<Vec<FileInfo>, Error> {
-    fs::read_dir(path).map(|entries| {
-        let mut file_infos: Vec<FileInfo> = entries
-            .filter_map(|result| result.ok())
-            .filter_map(|entry| {
-                let info = FileInfo::new(entry.path());
-                if !info.dir {
-                    // Do not show system files.
-                    if !info.path.is_file() {
-                        return None;
-                    }
-                }
-
-                #[cfg(unix)]
-                if info.get_file_name().starts_with('.') {
-                    return None;
-                }
-
-                Some(info)
-            })
-            .collect();
+/// Lists the entries of `folder` inside an archive, folders without an own entry are derived from the file paths.
+fn list_archive_folder(entries: Vec<(PathBuf, bool, ArchiveEntry)>, folder: &Path) -> Vec<FileEntry> {
+    let mut dirs: Vec<FileEntry> = Vec::new();
+    let mut files = Vec::new();
+    for (path, dir, archive_entry) in entries {
+        let Ok(relative) = path.strip_prefix(folder) else {
+            continue;
+        };
+        let mut components = relative.components();
+        let Some(first) = components.next() else {
+            continue;
+        };
+        let child = folder.join(first);
+        if dir || components.next().is_some() {
+            if !dirs.iter().any(|d| d.file_info.path == child) {
+                dirs.push(FileEntry {
+                    file_info: FileInfo {
+                        path: child,
+                        dir: true,
+                        ..Default::default()
+                    },
+                    file_data: None,
+                    archive_entry: None,
+                    read_sauce: false,
+                    sauce: None,
+                });
+            }
+        } else {
+            files.push(FileEntry {
+                file_info: FileInfo {
+                    path,
+                    dir: false,
+                    size: archive_entry.size,
+                    modified: None,
+                },
+                file_data: None,
+                archive_entry: Some(archive_entry),
+                read_sauce: false,
+                sauce: None,
+            });
+        }
+    }
+    dirs.sort_by(|a, b| a.file_info.path.file_name().cmp(&b.file_info.path.file_name()));
+    files.sort_by(|a, b| a.file_info.path.file_name().cmp(&b.file_info.path.file_name()));
+    dirs.append(&mut files);
+    dirs
+}
 
-        // Sort keeping folders before files.
-        file_infos.sort_by(|a, b| match a.dir == b.dir {
-            true => a.path.file_name().cmp(&b.path.file_name()),
-            false => b.dir.cmp(&a.dir),
-        });
+pub(crate) fn is_shown(info: &FileInfo) -> bool {
+    // Do not show system files.
+    if !info.dir && !info.path.is_file() {
+        return false;
+    }
 
-        #[cfg(windows)]
-        let file_infos = {
-            let drives = get_drives();
-            let mut infos = Vec::with_capacity(drives.len() + file_infos.len());
-            for drive in drives {
-                infos.push(FileInfo { path: drive, dir: true });
-            }
-            infos.append(&mut file_infos);
-            infos
-        };
+    #[cfg(unix)]
+    if info.get_file_name().starts_with('.') {
+        return false;
+    }
 
-        file_infos
-    })
+    true
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct FileInfo {
     pub path: PathBuf,
     pub dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
 }
 
 impl FileInfo {
     pub fn new(path: PathBuf) -> Self {
         let dir = path.is_dir();
-        Self { path, dir }
+        let metadata = fs::metadata(&path).ok();
+        let size = metadata.as_ref().map(|metadata| metadata.len()).unwrap_or_default();
+        let modified = metadata.and_then(|metadata| metadata.modified().ok());
+        Self { path, dir, size, modified }
     }
 
     pub fn get_file_name(&self) -> &str {
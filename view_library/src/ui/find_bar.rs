@@ -0,0 +1,229 @@
+use eframe::{
+    egui::{self, TextEdit, Ui},
+    epaint::{Color32, Rect, Rounding, Stroke, Vec2},
+};
+use i18n_embed_fl::fl;
+use icy_engine::{Buffer, Position};
+use icy_engine_gui::TerminalCalc;
+
+use super::keymap::{Action, Keymap};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FindMatch {
+    pub x: i32,
+    pub y: i32,
+    pub len: i32,
+}
+
+pub enum FindMessage {
+    Changed,
+    Next,
+    Previous,
+    Close,
+}
+
+/// Find bar shown above the buffer view, matches are searched line by line.
+pub struct FindBar {
+    text: String,
+    case_sensitive: bool,
+    matches: Vec<FindMatch>,
+    current: Option<usize>,
+    /// False if the buffer changed since the last search.
+    searched: bool,
+    request_focus: bool,
+    pending_message: Option<FindMessage>,
+}
+
+impl Default for FindBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FindBar {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            case_sensitive: false,
+            matches: Vec::new(),
+            current: None,
+            searched: false,
+            request_focus: true,
+            pending_message: None,
+        }
+    }
+
+    pub fn request_focus(&mut self) {
+        self.request_focus = true;
+    }
+
+    /// Queues a message that's returned by the next `show` call, used for the F3 keys.
+    pub fn set_pending_message(&mut self, message: FindMessage) {
+        self.pending_message = Some(message);
+    }
+
+    /// Drops the matches, called when another buffer got loaded.
+    pub fn invalidate(&mut self) {
+        self.matches.clear();
+        self.current = None;
+        self.searched = false;
+    }
+
+    pub fn needs_search(&self) -> bool {
+        !self.searched
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, keymap: &Keymap) -> Option<FindMessage> {
+        let mut message = self.pending_message.take();
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                TextEdit::singleline(&mut self.text)
+                    .hint_text(fl!(crate::LANGUAGE_LOADER, "find-hint-text"))
+                    .desired_width(250.0),
+            );
+            if self.request_focus {
+                response.request_focus();
+                self.request_focus = false;
+            }
+            if response.changed() {
+                message = Some(FindMessage::Changed);
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                message = Some(if ui.input(|i| i.modifiers.shift) {
+                    FindMessage::Previous
+                } else {
+                    FindMessage::Next
+                });
+                response.request_focus();
+            }
+
+            if ui
+                .checkbox(&mut self.case_sensitive, fl!(crate::LANGUAGE_LOADER, "find-case-sensitive"))
+                .changed()
+            {
+                message = Some(FindMessage::Changed);
+            }
+            let tooltip = keymap.format_tooltip(ui.ctx(), fl!(crate::LANGUAGE_LOADER, "tooltip-find-previous"), Action::FindPrevious);
+            if ui.button("⏶").on_hover_text(tooltip).clicked() {
+                message = Some(FindMessage::Previous);
+            }
+            let tooltip = keymap.format_tooltip(ui.ctx(), fl!(crate::LANGUAGE_LOADER, "tooltip-find-next"), Action::FindNext);
+            if ui.button("⏷").on_hover_text(tooltip).clicked() {
+                message = Some(FindMessage::Next);
+            }
+
+            if !self.text.is_empty() {
+                if self.matches.is_empty() {
+                    ui.colored_label(ui.style().visuals.warn_fg_color, fl!(crate::LANGUAGE_LOADER, "find-no-matches"));
+                } else {
+                    ui.label(fl!(
+                        crate::LANGUAGE_LOADER,
+                        "find-match-count",
+                        current = self.current.map_or(0, |current| current + 1),
+                        total = self.matches.len()
+                    ));
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("🗙").clicked() {
+                    message = Some(FindMessage::Close);
+                }
+            });
+        });
+        message
+    }
+
+    /// Searches all lines of the buffer, the current match is the first one at or below `from_line`.
+    pub fn search(&mut self, buffer: &Buffer, from_line: i32) -> Option<FindMatch> {
+        self.matches.clear();
+        self.current = None;
+        self.searched = true;
+
+        let needle: Vec<char> = self.text.chars().map(|ch| self.normalize(ch)).collect();
+        if needle.is_empty() {
+            return None;
+        }
+
+        let width = buffer.get_width();
+        for y in 0..buffer.get_line_count() {
+            let line: Vec<char> = (0..width)
+                .map(|x| self.normalize(buffer.buffer_type.convert_to_unicode(buffer.get_char(Position::new(x, y)))))
+                .collect();
+            let mut x = 0;
+            while x + needle.len() <= line.len() {
+                if line[x..x + needle.len()] == needle[..] {
+                    self.matches.push(FindMatch {
+                        x: x as i32,
+                        y,
+                        len: needle.len() as i32,
+                    });
+                    x += needle.len();
+                } else {
+                    x += 1;
+                }
+            }
+        }
+
+        if self.matches.is_empty() {
+            return None;
+        }
+        let current = self.matches.iter().position(|m| m.y >= from_line).unwrap_or(0);
+        self.current = Some(current);
+        Some(self.matches[current])
+    }
+
+    pub fn next(&mut self) -> Option<FindMatch> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        let current = self.current.map_or(0, |current| (current + 1) % self.matches.len());
+        self.current = Some(current);
+        Some(self.matches[current])
+    }
+
+    pub fn previous(&mut self) -> Option<FindMatch> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        let current = self.current.map_or(
+            self.matches.len() - 1,
+            |current| {
+                if current == 0 {
+                    self.matches.len() - 1
+                } else {
+                    current - 1
+                }
+            },
+        );
+        self.current = Some(current);
+        Some(self.matches[current])
+    }
+
+    /// Paints all matches over the buffer view, the current match gets an outline.
+    pub fn paint_matches(&self, ui: &Ui, calc: &TerminalCalc) {
+        let painter = ui.painter_at(calc.buffer_rect);
+        let origin = calc.buffer_rect.min - calc.char_scroll_position;
+        for (i, m) in self.matches.iter().enumerate() {
+            let rect = Rect::from_min_size(
+                origin + Vec2::new(m.x as f32 * calc.char_size.x, m.y as f32 * calc.char_size.y),
+                Vec2::new(m.len as f32 * calc.char_size.x, calc.char_size.y),
+            );
+            if !rect.intersects(calc.buffer_rect) {
+                continue;
+            }
+            painter.rect_filled(rect, Rounding::ZERO, Color32::from_rgba_unmultiplied(255, 220, 0, 90));
+            if Some(i) == self.current {
+                painter.rect_stroke(rect.expand(1.0), Rounding::ZERO, Stroke::new(2.0, Color32::from_rgb(255, 140, 0)));
+            }
+        }
+    }
+
+    fn normalize(&self, ch: char) -> char {
+        if self.case_sensitive {
+            ch
+        } else {
+            ch.to_lowercase().next().unwrap_or(ch)
+        }
+    }
+}
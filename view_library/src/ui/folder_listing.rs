@@ -0,0 +1,69 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use super::file_view::{is_shown, FileInfo};
+
+/// Reads a folder on a worker thread and streams its entries, reading the metadata is slow on network shares.
+pub struct FolderListing {
+    cancelled: Arc<AtomicBool>,
+    entry_rx: Receiver<FileInfo>,
+    handle: JoinHandle<()>,
+}
+
+impl FolderListing {
+    pub fn start(path: PathBuf) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (entry_tx, entry_rx) = channel();
+
+        let thread_cancelled = cancelled.clone();
+        let handle = thread::spawn(move || list_folder(&path, &thread_cancelled, &entry_tx));
+
+        Self { cancelled, entry_rx, handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the entries read since the last call.
+    pub fn poll(&self) -> Vec<FileInfo> {
+        self.entry_rx.try_iter().collect()
+    }
+}
+
+impl Drop for FolderListing {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+fn list_folder(path: &Path, cancelled: &AtomicBool, entry_tx: &Sender<FileInfo>) {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::error!("Failed to read folder: {}", err);
+            return;
+        }
+    };
+    for entry in entries.filter_map(|result| result.ok()) {
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        let info = FileInfo::new(entry.path());
+        if is_shown(&info) && entry_tx.send(info).is_err() {
+            return;
+        }
+    }
+}
@@ -1,10 +1,9 @@
-use eframe::{
-    egui::{self},
-    epaint::FontId,
-};
+use eframe::egui;
 use egui_modal::Modal;
 use i18n_embed_fl::fl;
 
+use super::keymap::{Action, Keymap};
+
 pub struct HelpDialog {}
 
 pub enum Message {
@@ -16,21 +15,28 @@ impl HelpDialog {
         Self {}
     }
 
-    pub fn show(&mut self, ctx: &egui::Context) -> Option<Message> {
+    /// Lists the shortcuts of the active keymap, unbound actions are left out.
+    pub fn show(&mut self, ctx: &egui::Context, keymap: &Keymap) -> Option<Message> {
         let mut message = None;
-        let modal = Modal::new(ctx, "protocol_modal");
+        let modal = Modal::new(ctx, "help_modal");
         modal.show(|ui| {
             modal.title(ui, fl!(crate::LANGUAGE_LOADER, "help-dialog-title"));
 
             modal.frame(ui, |ui: &mut egui::Ui| {
-                let help = fl!(crate::LANGUAGE_LOADER, "help-dialog-text");
-                egui::ScrollArea::vertical().max_height(180.0).show(ui, |ui| {
-                    ui.add(
-                        egui::TextEdit::multiline(&mut help.as_str())
-                            .font(FontId::new(18.0, egui::FontFamily::Proportional))
-                            .desired_rows(6)
-                            .desired_width(f32::INFINITY),
-                    );
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("help_grid").num_columns(2).striped(true).show(ui, |ui| {
+                        for action in Action::ALL {
+                            let shortcuts: Vec<String> = keymap.get_shortcuts(action).map(|shortcut| ctx.format_shortcut(&shortcut)).collect();
+                            if shortcuts.is_empty() {
+                                continue;
+                            }
+                            ui.strong(shortcuts.join(", "));
+                            ui.label(action.get_description());
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(8.0);
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "help-dialog-mouse-text"));
                 });
             });
 
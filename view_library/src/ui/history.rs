@@ -0,0 +1,107 @@
+/// Entries that are kept for going back, older ones get dropped.
+const MAX_HISTORY: usize = 100;
+
+/// Back and forward stacks of visited locations.
+pub struct History<T> {
+    back: Vec<T>,
+    forward: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Remembers the location that is left, going somewhere new drops the forward entries.
+    pub fn push(&mut self, location: T) {
+        if self.back.len() >= MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.back.push(location);
+        self.forward.clear();
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    /// Returns the previous location, `current` becomes the next forward entry.
+    pub fn back(&mut self, current: T) -> Option<T> {
+        let location = self.back.pop()?;
+        self.forward.push(current);
+        Some(location)
+    }
+
+    /// Returns the next location, `current` becomes the next back entry.
+    pub fn forward(&mut self, current: T) -> Option<T> {
+        let location = self.forward.pop()?;
+        self.back.push(current);
+        Some(location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_history() {
+        let mut history = History::default();
+        assert!(!history.can_go_back());
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(1), None);
+        assert_eq!(history.forward(1), None);
+        // a failed step doesn't record the current location
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut history = History::default();
+        history.push(1);
+        history.push(2);
+        assert_eq!(history.back(3), Some(2));
+        assert_eq!(history.back(2), Some(1));
+        assert!(!history.can_go_back());
+        assert_eq!(history.forward(1), Some(2));
+        assert_eq!(history.forward(2), Some(3));
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(3), Some(2));
+    }
+
+    #[test]
+    fn test_push_clears_forward() {
+        let mut history = History::default();
+        history.push(1);
+        assert_eq!(history.back(2), Some(1));
+        assert!(history.can_go_forward());
+        history.push(1);
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(4), Some(1));
+    }
+
+    #[test]
+    fn test_max_history() {
+        let mut history = History::default();
+        for i in 0..MAX_HISTORY + 10 {
+            history.push(i);
+        }
+        let mut current = MAX_HISTORY + 10;
+        let mut steps = 0;
+        while let Some(location) = history.back(current) {
+            current = location;
+            steps += 1;
+        }
+        assert_eq!(steps, MAX_HISTORY);
+        assert_eq!(current, 10);
+    }
+}
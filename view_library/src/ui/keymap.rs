@@ -0,0 +1,477 @@
+use eframe::egui::{Context, Event, Key, KeyboardShortcut, Modifiers};
+use i18n_embed_fl::fl;
+
+use super::options::Options;
+
+/// Everything that can be bound to a key, the name is the key of the `[keys]` table in options.toml.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    ShowHelp,
+    ToggleAutoScroll,
+    ChangeScrollSpeed,
+    ShowSauce,
+    ToggleSlideshow,
+    TogglePlayback,
+    ToggleFullScreen,
+    CloseDialog,
+    Quit,
+
+    ParentFolder,
+    Back,
+    Forward,
+    QuickJump,
+    SelectPrevious,
+    SelectNext,
+    SelectLeft,
+    SelectRight,
+    SelectFirst,
+    SelectLast,
+    SelectPageUp,
+    SelectPageDown,
+    Open,
+
+    Find,
+    FindNext,
+    FindPrevious,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    ScrollTop,
+    ScrollBottom,
+    ScrollUp,
+    ScrollDown,
+    ScrollPageUp,
+    ScrollPageDown,
+
+    SaveAs,
+    ExportPng,
+    ExportPng2x,
+    ExportGif,
+    CopyImage,
+    ToggleMark,
+    CopyFiles,
+    MoveFiles,
+    RenameFile,
+    TrashFiles,
+}
+
+impl Action {
+    /// All actions in the order the help lists them.
+    pub const ALL: [Action; 44] = [
+        Action::ShowHelp,
+        Action::ToggleAutoScroll,
+        Action::ChangeScrollSpeed,
+        Action::ShowSauce,
+        Action::ToggleSlideshow,
+        Action::TogglePlayback,
+        Action::ToggleFullScreen,
+        Action::CloseDialog,
+        Action::Quit,
+        Action::ParentFolder,
+        Action::Back,
+        Action::Forward,
+        Action::QuickJump,
+        Action::SelectPrevious,
+        Action::SelectNext,
+        Action::SelectLeft,
+        Action::SelectRight,
+        Action::SelectFirst,
+        Action::SelectLast,
+        Action::SelectPageUp,
+        Action::SelectPageDown,
+        Action::Open,
+        Action::Find,
+        Action::FindNext,
+        Action::FindPrevious,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ZoomReset,
+        Action::ScrollTop,
+        Action::ScrollBottom,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ScrollPageUp,
+        Action::ScrollPageDown,
+        Action::SaveAs,
+        Action::ExportPng,
+        Action::ExportPng2x,
+        Action::ExportGif,
+        Action::CopyImage,
+        Action::ToggleMark,
+        Action::CopyFiles,
+        Action::MoveFiles,
+        Action::RenameFile,
+        Action::TrashFiles,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::ShowHelp => "show_help",
+            Action::ToggleAutoScroll => "toggle_auto_scroll",
+            Action::ChangeScrollSpeed => "change_scroll_speed",
+            Action::ShowSauce => "show_sauce",
+            Action::ToggleSlideshow => "toggle_slideshow",
+            Action::TogglePlayback => "toggle_playback",
+            Action::ToggleFullScreen => "toggle_full_screen",
+            Action::CloseDialog => "close_dialog",
+            Action::Quit => "quit",
+            Action::ParentFolder => "parent_folder",
+            Action::Back => "back",
+            Action::Forward => "forward",
+            Action::QuickJump => "quick_jump",
+            Action::SelectPrevious => "select_previous",
+            Action::SelectNext => "select_next",
+            Action::SelectLeft => "select_left",
+            Action::SelectRight => "select_right",
+            Action::SelectFirst => "select_first",
+            Action::SelectLast => "select_last",
+            Action::SelectPageUp => "select_page_up",
+            Action::SelectPageDown => "select_page_down",
+            Action::Open => "open",
+            Action::Find => "find",
+            Action::FindNext => "find_next",
+            Action::FindPrevious => "find_previous",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::ZoomReset => "zoom_reset",
+            Action::ScrollTop => "scroll_top",
+            Action::ScrollBottom => "scroll_bottom",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollPageUp => "scroll_page_up",
+            Action::ScrollPageDown => "scroll_page_down",
+            Action::SaveAs => "save_as",
+            Action::ExportPng => "export_png",
+            Action::ExportPng2x => "export_png_2x",
+            Action::ExportGif => "export_gif",
+            Action::CopyImage => "copy_image",
+            Action::ToggleMark => "toggle_mark",
+            Action::CopyFiles => "copy_files",
+            Action::MoveFiles => "move_files",
+            Action::RenameFile => "rename_file",
+            Action::TrashFiles => "trash_files",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.get_name() == name)
+    }
+
+    pub fn get_description(&self) -> String {
+        match self {
+            Action::ShowHelp => fl!(crate::LANGUAGE_LOADER, "keymap-show-help"),
+            Action::ToggleAutoScroll => fl!(crate::LANGUAGE_LOADER, "keymap-toggle-auto-scroll"),
+            Action::ChangeScrollSpeed => fl!(crate::LANGUAGE_LOADER, "keymap-change-scroll-speed"),
+            Action::ShowSauce => fl!(crate::LANGUAGE_LOADER, "keymap-show-sauce"),
+            Action::ToggleSlideshow => fl!(crate::LANGUAGE_LOADER, "keymap-toggle-slideshow"),
+            Action::TogglePlayback => fl!(crate::LANGUAGE_LOADER, "keymap-toggle-playback"),
+            Action::ToggleFullScreen => fl!(crate::LANGUAGE_LOADER, "keymap-toggle-full-screen"),
+            Action::CloseDialog => fl!(crate::LANGUAGE_LOADER, "keymap-close-dialog"),
+            Action::Quit => fl!(crate::LANGUAGE_LOADER, "keymap-quit"),
+            Action::ParentFolder => fl!(crate::LANGUAGE_LOADER, "keymap-parent-folder"),
+            Action::Back => fl!(crate::LANGUAGE_LOADER, "keymap-back"),
+            Action::Forward => fl!(crate::LANGUAGE_LOADER, "keymap-forward"),
+            Action::QuickJump => fl!(crate::LANGUAGE_LOADER, "keymap-quick-jump"),
+            Action::SelectPrevious => fl!(crate::LANGUAGE_LOADER, "keymap-select-previous"),
+            Action::SelectNext => fl!(crate::LANGUAGE_LOADER, "keymap-select-next"),
+            Action::SelectLeft => fl!(crate::LANGUAGE_LOADER, "keymap-select-left"),
+            Action::SelectRight => fl!(crate::LANGUAGE_LOADER, "keymap-select-right"),
+            Action::SelectFirst => fl!(crate::LANGUAGE_LOADER, "keymap-select-first"),
+            Action::SelectLast => fl!(crate::LANGUAGE_LOADER, "keymap-select-last"),
+            Action::SelectPageUp => fl!(crate::LANGUAGE_LOADER, "keymap-select-page-up"),
+            Action::SelectPageDown => fl!(crate::LANGUAGE_LOADER, "keymap-select-page-down"),
+            Action::Open => fl!(crate::LANGUAGE_LOADER, "keymap-open"),
+            Action::Find => fl!(crate::LANGUAGE_LOADER, "keymap-find"),
+            Action::FindNext => fl!(crate::LANGUAGE_LOADER, "keymap-find-next"),
+            Action::FindPrevious => fl!(crate::LANGUAGE_LOADER, "keymap-find-previous"),
+            Action::ZoomIn => fl!(crate::LANGUAGE_LOADER, "keymap-zoom-in"),
+            Action::ZoomOut => fl!(crate::LANGUAGE_LOADER, "keymap-zoom-out"),
+            Action::ZoomReset => fl!(crate::LANGUAGE_LOADER, "keymap-zoom-reset"),
+            Action::ScrollTop => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-top"),
+            Action::ScrollBottom => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-bottom"),
+            Action::ScrollUp => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-up"),
+            Action::ScrollDown => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-down"),
+            Action::ScrollPageUp => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-page-up"),
+            Action::ScrollPageDown => fl!(crate::LANGUAGE_LOADER, "keymap-scroll-page-down"),
+            Action::SaveAs => fl!(crate::LANGUAGE_LOADER, "keymap-save-as"),
+            Action::ExportPng => fl!(crate::LANGUAGE_LOADER, "keymap-export-png"),
+            Action::ExportPng2x => fl!(crate::LANGUAGE_LOADER, "keymap-export-png-2x"),
+            Action::ExportGif => fl!(crate::LANGUAGE_LOADER, "keymap-export-gif"),
+            Action::CopyImage => fl!(crate::LANGUAGE_LOADER, "keymap-copy-image"),
+            Action::ToggleMark => fl!(crate::LANGUAGE_LOADER, "keymap-toggle-mark"),
+            Action::CopyFiles => fl!(crate::LANGUAGE_LOADER, "keymap-copy-files"),
+            Action::MoveFiles => fl!(crate::LANGUAGE_LOADER, "keymap-move-files"),
+            Action::RenameFile => fl!(crate::LANGUAGE_LOADER, "keymap-rename-file"),
+            Action::TrashFiles => fl!(crate::LANGUAGE_LOADER, "keymap-trash-files"),
+        }
+    }
+
+    fn get_default_shortcuts(&self) -> Vec<KeyboardShortcut> {
+        let key = |key| KeyboardShortcut::new(Modifiers::NONE, key);
+        let alt = |key| KeyboardShortcut::new(Modifiers::ALT, key);
+        let command = |key| KeyboardShortcut::new(Modifiers::COMMAND, key);
+        let command_shift = |key| KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), key);
+        match self {
+            Action::ShowHelp => vec![key(Key::F1)],
+            Action::ToggleAutoScroll => vec![key(Key::F2)],
+            Action::ChangeScrollSpeed => vec![key(Key::F3)],
+            Action::ShowSauce => vec![key(Key::F4)],
+            Action::ToggleSlideshow => vec![key(Key::F5)],
+            Action::TogglePlayback => vec![key(Key::F6)],
+            Action::ToggleFullScreen => vec![key(Key::F11), alt(Key::Enter)],
+            Action::CloseDialog => vec![key(Key::Escape)],
+            Action::Quit => vec![alt(Key::Q), key(Key::Escape)],
+            Action::ParentFolder => vec![alt(Key::PageUp)],
+            Action::Back => vec![alt(Key::ArrowLeft)],
+            Action::Forward => vec![alt(Key::ArrowRight)],
+            Action::QuickJump => vec![command(Key::J)],
+            Action::SelectPrevious => vec![key(Key::ArrowUp)],
+            Action::SelectNext => vec![key(Key::ArrowDown)],
+            Action::SelectLeft => vec![key(Key::ArrowLeft)],
+            Action::SelectRight => vec![key(Key::ArrowRight)],
+            Action::SelectFirst => vec![key(Key::Home)],
+            Action::SelectLast => vec![key(Key::End)],
+            Action::SelectPageUp => vec![key(Key::PageUp)],
+            Action::SelectPageDown => vec![key(Key::PageDown)],
+            Action::Open => vec![key(Key::Enter)],
+            Action::Find => vec![command(Key::F)],
+            Action::FindNext => vec![key(Key::F3)],
+            Action::FindPrevious => vec![KeyboardShortcut::new(Modifiers::SHIFT, Key::F3)],
+            Action::ZoomIn => vec![command(Key::Plus), command(Key::Equals)],
+            Action::ZoomOut => vec![command(Key::Minus)],
+            Action::ZoomReset => vec![command(Key::Num0)],
+            Action::ScrollTop => vec![command(Key::Home)],
+            Action::ScrollBottom => vec![command(Key::End)],
+            Action::ScrollUp => vec![command(Key::ArrowUp)],
+            Action::ScrollDown => vec![command(Key::ArrowDown)],
+            Action::ScrollPageUp => vec![command(Key::PageUp)],
+            Action::ScrollPageDown => vec![command(Key::PageDown)],
+            Action::SaveAs => vec![command(Key::S)],
+            Action::ExportPng => vec![command(Key::E)],
+            Action::ExportPng2x => vec![command_shift(Key::E)],
+            Action::ExportGif => vec![command(Key::G)],
+            Action::CopyImage => vec![command_shift(Key::C)],
+            Action::ToggleMark => vec![key(Key::Space)],
+            Action::CopyFiles => vec![key(Key::F7)],
+            Action::MoveFiles => vec![key(Key::F8)],
+            Action::RenameFile => vec![key(Key::F9)],
+            Action::TrashFiles => vec![key(Key::Delete)],
+        }
+    }
+}
+
+/// Parses shortcuts like `"F1"`, `"Alt+Left"` or `"Ctrl+Shift+E"`, key names are the ones egui uses.
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let text = text.trim();
+    // "Ctrl++" binds the plus key
+    let (modifier_names, key_name) = match text.strip_suffix('+') {
+        Some(rest) if rest.is_empty() || rest.ends_with('+') => (rest.strip_suffix('+').unwrap_or_default(), "+"),
+        _ => text.rsplit_once('+').unwrap_or(("", text)),
+    };
+
+    let mut modifiers = Modifiers::NONE;
+    if !modifier_names.is_empty() {
+        for name in modifier_names.split('+') {
+            let modifier = match name.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+                "alt" | "option" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                _ => return None,
+            };
+            modifiers = modifiers.plus(modifier);
+        }
+    }
+    Key::from_name(key_name.trim()).map(|key| KeyboardShortcut::new(modifiers, key))
+}
+
+/// Maps key presses to actions, the defaults can be overridden in the `[keys]` table of the options.
+pub struct Keymap {
+    bindings: Vec<(Action, KeyboardShortcut)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .flat_map(|action| action.get_default_shortcuts().into_iter().map(|shortcut| (*action, shortcut)))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn new(options: &Options) -> Self {
+        let mut keymap = Self::default();
+        for (name, binding) in &options.keys {
+            let Some(action) = Action::from_name(name) else {
+                log::error!("Unknown action in [keys]: {name}");
+                continue;
+            };
+            keymap.bindings.retain(|(bound_action, _)| *bound_action != action);
+            for text in binding.get_shortcuts() {
+                match parse_shortcut(text) {
+                    Some(shortcut) => keymap.bindings.push((action, shortcut)),
+                    None => log::error!("Invalid shortcut for {name}: {text}"),
+                }
+            }
+        }
+        keymap
+    }
+
+    pub fn get_shortcuts(&self, action: Action) -> impl Iterator<Item = KeyboardShortcut> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(bound_action, _)| *bound_action == action)
+            .map(|(_, shortcut)| *shortcut)
+    }
+
+    /// Text of the first shortcut of `action` for menu buttons, empty if the action isn't bound.
+    pub fn format_shortcut(&self, ctx: &Context, action: Action) -> String {
+        self.get_shortcuts(action)
+            .next()
+            .map(|shortcut| ctx.format_shortcut(&shortcut))
+            .unwrap_or_default()
+    }
+
+    /// Appends the first shortcut of `action` to a tooltip, like `Back (Alt+Left)`.
+    pub fn format_tooltip(&self, ctx: &Context, text: String, action: Action) -> String {
+        match self.format_shortcut(ctx, action) {
+            shortcut if shortcut.is_empty() => text,
+            shortcut => format!("{text} ({shortcut})"),
+        }
+    }
+
+    /// Was a key of `action` pressed this frame? Includes key repeats.
+    pub fn pressed(&self, ctx: &Context, action: Action) -> bool {
+        ctx.input(|i| i.events.iter().any(|event| self.matches(event, action)))
+    }
+
+    /// Like [`Self::pressed`], but removes the key press so later checks don't see it again.
+    pub fn consume(&self, ctx: &Context, action: Action) -> bool {
+        ctx.input_mut(|i| {
+            let count = i.events.len();
+            i.events.retain(|event| !self.matches(event, action));
+            i.events.len() != count
+        })
+    }
+
+    fn matches(&self, event: &Event, action: Action) -> bool {
+        let Event::Key {
+            key, pressed: true, modifiers, ..
+        } = event
+        else {
+            return false;
+        };
+        match self.get_best_shortcut(*key, *modifiers) {
+            Some(best) => self.get_shortcuts(action).any(|shortcut| shortcut == best),
+            None => false,
+        }
+    }
+
+    /// Extra shift and alt modifiers are ignored like egui does, but the shortcut that matches
+    /// the most modifiers wins, so Ctrl+Shift+E doesn't trigger the action bound to Ctrl+E.
+    fn get_best_shortcut(&self, key: Key, modifiers: Modifiers) -> Option<KeyboardShortcut> {
+        self.bindings
+            .iter()
+            .map(|(_, shortcut)| *shortcut)
+            .filter(|shortcut| shortcut.logical_key == key && modifiers.matches_logically(shortcut.modifiers))
+            .max_by_key(|shortcut| shortcut.modifiers.alt as u8 + shortcut.modifiers.shift as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::options::KeyBinding;
+
+    fn shortcut(modifiers: Modifiers, key: Key) -> Option<KeyboardShortcut> {
+        Some(KeyboardShortcut::new(modifiers, key))
+    }
+
+    /// Modifiers egui reports for a pressed Ctrl key on Windows and Linux.
+    fn ctrl() -> Modifiers {
+        Modifiers {
+            ctrl: true,
+            command: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_shortcut() {
+        assert_eq!(parse_shortcut("F1"), shortcut(Modifiers::NONE, Key::F1));
+        assert_eq!(parse_shortcut(" Alt+ArrowLeft "), shortcut(Modifiers::ALT, Key::ArrowLeft));
+        assert_eq!(parse_shortcut("Ctrl+Shift+E"), shortcut(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::E));
+        assert_eq!(parse_shortcut("cmd+option+Q"), shortcut(Modifiers::COMMAND.plus(Modifiers::ALT), Key::Q));
+    }
+
+    #[test]
+    fn test_parse_plus_key() {
+        assert_eq!(parse_shortcut("+"), shortcut(Modifiers::NONE, Key::Plus));
+        assert_eq!(parse_shortcut("Ctrl++"), shortcut(Modifiers::COMMAND, Key::Plus));
+    }
+
+    #[test]
+    fn test_parse_invalid_shortcut() {
+        assert_eq!(parse_shortcut(""), None);
+        assert_eq!(parse_shortcut("NoSuchKey"), None);
+        assert_eq!(parse_shortcut("Hyper+F1"), None);
+        assert_eq!(parse_shortcut("Ctrl+"), None);
+    }
+
+    #[test]
+    fn test_best_shortcut_prefers_more_modifiers() {
+        let keymap = Keymap::default();
+        let ctrl_shift = ctrl().plus(Modifiers::SHIFT);
+        assert_eq!(keymap.get_best_shortcut(Key::E, ctrl()), shortcut(Modifiers::COMMAND, Key::E));
+        assert_eq!(
+            keymap.get_best_shortcut(Key::E, ctrl_shift),
+            shortcut(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::E)
+        );
+        assert_eq!(keymap.get_best_shortcut(Key::F3, Modifiers::SHIFT), shortcut(Modifiers::SHIFT, Key::F3));
+        assert_eq!(keymap.get_best_shortcut(Key::F3, Modifiers::NONE), shortcut(Modifiers::NONE, Key::F3));
+    }
+
+    #[test]
+    fn test_best_shortcut_ignores_extra_shift() {
+        // Ctrl+Shift+J has no binding of its own, Ctrl+J is used
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.get_best_shortcut(Key::J, ctrl().plus(Modifiers::SHIFT)),
+            shortcut(Modifiers::COMMAND, Key::J)
+        );
+    }
+
+    #[test]
+    fn test_best_shortcut_needs_modifiers() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.get_best_shortcut(Key::J, Modifiers::NONE), None);
+        assert_eq!(keymap.get_best_shortcut(Key::Q, Modifiers::NONE), None);
+        // Ctrl doesn't match shortcuts without it
+        assert_eq!(keymap.get_best_shortcut(Key::F1, ctrl()), None);
+    }
+
+    #[test]
+    fn test_options_override_defaults() {
+        let mut options = Options::default();
+        options.keys.insert("show_help".to_string(), KeyBinding::Single("Ctrl+H".to_string()));
+        options.keys.insert(
+            "toggle_full_screen".to_string(),
+            KeyBinding::Multiple(vec!["F12".to_string(), "invalid+key".to_string()]),
+        );
+        options.keys.insert("no_such_action".to_string(), KeyBinding::Single("F1".to_string()));
+        let keymap = Keymap::new(&options);
+
+        assert_eq!(
+            keymap.get_shortcuts(Action::ShowHelp).collect::<Vec<_>>(),
+            vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::H)]
+        );
+        assert_eq!(
+            keymap.get_shortcuts(Action::ToggleFullScreen).collect::<Vec<_>>(),
+            vec![KeyboardShortcut::new(Modifiers::NONE, Key::F12)]
+        );
+        assert_eq!(keymap.get_best_shortcut(Key::F1, Modifiers::NONE), None);
+        assert_eq!(keymap.get_shortcuts(Action::Quit).count(), 2);
+    }
+}
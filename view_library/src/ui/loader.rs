@@ -0,0 +1,340 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use eframe::epaint::ColorImage;
+use icy_engine::{parse_with_parser, rip, Buffer, Rectangle};
+use icy_engine_gui::animations::Animator;
+use image::{imageops::FilterType, RgbaImage};
+
+use super::file_view::FileEntry;
+
+/// Vertical stretch applied to buffers that request the legacy aspect ratio.
+pub const ASPECT_RATIO_SCALE: f32 = 1.35;
+
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "bmp"];
+
+pub const EXT_WHITE_LIST: [&str; 5] = ["seq", "diz", "nfo", "ice", "bbs"];
+
+/// Time an icyanim file gets to load its frames before rendering it fails.
+const ANIMATION_LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn get_extension(path: &Path) -> String {
+    if let Some(ext) = path.extension() {
+        ext.to_ascii_lowercase().to_str().unwrap_or_default().to_string()
+    } else {
+        String::new()
+    }
+}
+
+pub fn is_image_extension(ext: &str) -> bool {
+    IMAGE_EXTENSIONS.contains(&ext)
+}
+
+pub fn is_engine_format(ext: &str) -> bool {
+    icy_engine::FORMATS
+        .iter()
+        .any(|f| f.get_file_extension() == ext || f.get_alt_extensions().contains(&ext.to_string()))
+}
+
+/// Files that can be rendered without guessing, used for thumbnails.
+pub fn has_preview(ext: &str) -> bool {
+    is_image_extension(ext) || ext == "rip" || ext == "icyanim" || EXT_WHITE_LIST.contains(&ext) || is_engine_format(ext)
+}
+
+pub fn parse_rip(_path: &Path, data: &[u8]) -> rip::Parser {
+    let mut rip_parser = rip::Parser::new(Box::default(), PathBuf::new());
+    let mut result: Buffer = Buffer::new((80, 25));
+    result.is_terminal_buffer = false;
+
+    let (text, is_unicode) = icy_engine::convert_ansi_to_utf8(data);
+    if is_unicode {
+        result.buffer_type = icy_engine::BufferType::Unicode;
+    }
+
+    if let Err(err) = parse_with_parser(&mut result, &mut rip_parser, &text, true) {
+        log::error!("Error while parsing rip file: {err}");
+    }
+    rip_parser
+}
+
+/// Converts the BGI screen of a parsed RIP file to RGBA pixels.
+pub fn rip_to_rgba(parser: &rip::Parser) -> (u32, u32, Vec<u8>) {
+    let size = parser.bgi.window;
+    let pal = parser.bgi.get_palette();
+    let mut pixels = Vec::with_capacity(parser.bgi.screen.len() * 4);
+    for i in &parser.bgi.screen {
+        let (r, g, b) = pal.get_rgb(*i as u32);
+        pixels.push(r);
+        pixels.push(g);
+        pixels.push(b);
+        pixels.push(255);
+    }
+    (size.width as u32, size.height as u32, pixels)
+}
+
+pub fn rip_to_color_image(parser: &rip::Parser) -> ColorImage {
+    let (width, height, pixels) = rip_to_rgba(parser);
+    ColorImage::from_rgba_premultiplied([width as usize, height as usize], &pixels)
+}
+
+pub fn load_animation(path: &Path, data: &[u8]) -> anyhow::Result<Arc<Mutex<Animator>>> {
+    match String::from_utf8(data.to_vec()) {
+        Ok(data) => {
+            let parent = path.parent().map(|path| path.to_path_buf());
+            Ok(Animator::run(&parent, data))
+        }
+        Err(err) => {
+            log::error!("Error while parsing icyanim file: {err}");
+            Err(anyhow::anyhow!("Error while parsing icyanim file: {err}"))
+        }
+    }
+}
+
+/// Renders a buffer the way the buffer view shows it, including 9px letter spacing and the aspect ratio correction.
+pub fn render_buffer(buf: &Buffer) -> anyhow::Result<RgbaImage> {
+    render_lines(buf, 0, buf.get_line_count())
+}
+
+/// Renders `height` lines starting at `first_line`, see `render_buffer`.
+pub fn render_lines(buf: &Buffer, first_line: i32, height: i32) -> anyhow::Result<RgbaImage> {
+    let (size, pixels) = buf.render_to_rgba(Rectangle::from(0, first_line, buf.get_width(), height));
+    let Some(mut img) = RgbaImage::from_raw(size.width as u32, size.height as u32, pixels) else {
+        return Err(anyhow::anyhow!("Invalid image size {}x{}", size.width, size.height));
+    };
+
+    if buf.use_letter_spacing() {
+        img = apply_letter_spacing(buf, &img, first_line);
+    }
+
+    if buf.use_aspect_ratio() {
+        let height = (img.height() as f32 * ASPECT_RATIO_SCALE).round() as u32;
+        img = image::imageops::resize(&img, img.width(), height, FilterType::Triangle);
+    }
+    Ok(img)
+}
+
+/// Inserts the 9th pixel column VGA adds to every character cell.
+/// Line drawing characters (0xC0-0xDF) repeat their last column, all others get the background color.
+fn apply_letter_spacing(buf: &Buffer, img: &RgbaImage, first_line: i32) -> RgbaImage {
+    let font_size = buf.get_font_dimensions();
+    let font_width = font_size.width.max(1) as u32;
+    let font_height = font_size.height.max(1) as u32;
+    let columns = img.width() / font_width;
+    let mut result = RgbaImage::new(columns * (font_width + 1), img.height());
+
+    for y in 0..img.height() {
+        let line = first_line + (y / font_height) as i32;
+        for x in 0..columns {
+            for px in 0..font_width {
+                result.put_pixel(x * (font_width + 1) + px, y, *img.get_pixel(x * font_width + px, y));
+            }
+            let ch = buf.get_char((x as i32, line));
+            let spacing = if (0xC0..=0xDF).contains(&(ch.ch as u32)) {
+                *img.get_pixel(x * font_width + font_width - 1, y)
+            } else {
+                let (r, g, b) = buf.palette.get_rgb(ch.attribute.get_background());
+                image::Rgba([r, g, b, 255])
+            };
+            result.put_pixel(x * (font_width + 1) + font_width, y, spacing);
+        }
+    }
+    result
+}
+
+/// Loads a file the same way the viewer does and renders it to an image.
+pub fn render_file(path: &Path) -> anyhow::Result<RgbaImage> {
+    let data = std::fs::read(path)?;
+    render_data(path, &data)
+}
+
+pub fn render_data(path: &Path, data: &[u8]) -> anyhow::Result<RgbaImage> {
+    let ext = get_extension(path);
+
+    if is_image_extension(&ext) {
+        return Ok(image::load_from_memory(data)?.to_rgba8());
+    }
+
+    if ext == "rip" {
+        let parser = parse_rip(path, data);
+        let (width, height, pixels) = rip_to_rgba(&parser);
+        return RgbaImage::from_raw(width, height, pixels).ok_or_else(|| anyhow::anyhow!("Invalid rip screen size {width}x{height}"));
+    }
+
+    if ext == "icyanim" {
+        let anim = load_animation(path, data)?;
+        // the animator parses its frames on a separate thread
+        let start = Instant::now();
+        while !anim.lock().unwrap().success() {
+            if !anim.lock().unwrap().error.is_empty() {
+                return Err(anyhow::anyhow!("Error while loading icyanim file: {}", anim.lock().unwrap().error));
+            }
+            if start.elapsed() > ANIMATION_LOAD_TIMEOUT {
+                return Err(anyhow::anyhow!("Loading the icyanim file timed out"));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let anim = anim.lock().unwrap();
+        let Some((buf, _, _)) = anim.frames.first() else {
+            return Err(anyhow::anyhow!("Animation has no frames"));
+        };
+        return render_buffer(buf);
+    }
+
+    let buf = Buffer::from_bytes(path, true, data)?;
+    render_buffer(&buf)
+}
+
+pub enum LoadedContent {
+    Buffer(Buffer),
+    RipImage(ColorImage),
+    /// The file looks binary and was not parsed, it's loaded on request.
+    Binary,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoadingProgress {
+    Reading,
+    Parsing,
+}
+
+struct LoadingRequest {
+    generation: usize,
+    entry: FileEntry,
+    check_binary: bool,
+}
+
+struct LoadingResult {
+    generation: usize,
+    content: anyhow::Result<LoadedContent>,
+}
+
+/// Parses file entries on a single worker thread so large files don't block the UI.
+/// Only the newest request is parsed, requests that are replaced while waiting are dropped.
+pub struct LoadingWorker {
+    /// Name of the file that is loading, `None` if nothing is requested.
+    file_name: Option<String>,
+    generation: Arc<AtomicUsize>,
+    progress: Arc<Mutex<LoadingProgress>>,
+    request_tx: Sender<LoadingRequest>,
+    result_rx: Receiver<LoadingResult>,
+}
+
+impl Default for LoadingWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadingWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = channel::<LoadingRequest>();
+        let (result_tx, result_rx) = channel();
+        let generation = Arc::new(AtomicUsize::new(0));
+        let progress = Arc::new(Mutex::new(LoadingProgress::Reading));
+
+        let thread_generation = generation.clone();
+        let thread_progress = progress.clone();
+        thread::spawn(move || {
+            while let Ok(mut request) = request_rx.recv() {
+                // skip to the newest request, e.g. when the selection moves while a key is held
+                while let Ok(newer) = request_rx.try_recv() {
+                    request = newer;
+                }
+                let is_current = || request.generation == thread_generation.load(Ordering::Relaxed);
+                if !is_current() {
+                    continue;
+                }
+                *thread_progress.lock().unwrap() = LoadingProgress::Reading;
+                let content = load_entry(&request.entry, request.check_binary, &thread_progress, is_current);
+                let result = LoadingResult {
+                    generation: request.generation,
+                    content,
+                };
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            file_name: None,
+            generation,
+            progress,
+            request_tx,
+            result_rx,
+        }
+    }
+
+    /// Requests loading `entry`, a running request is replaced.
+    /// With `check_binary` files that look binary aren't parsed, see `is_binary`.
+    pub fn start(&mut self, entry: FileEntry, check_binary: bool) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        self.file_name = Some(entry.file_info.get_file_name().to_string());
+        let request = LoadingRequest {
+            generation,
+            entry,
+            check_binary,
+        };
+        if self.request_tx.send(request).is_err() {
+            log::error!("The loading thread has stopped");
+            self.file_name = None;
+        }
+    }
+
+    /// The parser can't be interrupted, it finishes in the background and its result gets dropped.
+    pub fn cancel(&mut self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.file_name = None;
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.file_name.is_some()
+    }
+
+    pub fn get_file_name(&self) -> &str {
+        self.file_name.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_progress(&self) -> LoadingProgress {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Returns the content of the requested file once it's loaded, results of replaced requests are dropped.
+    pub fn poll(&mut self) -> Option<anyhow::Result<LoadedContent>> {
+        let generation = self.generation.load(Ordering::Relaxed);
+        let result = self.result_rx.try_iter().find(|result| result.generation == generation)?;
+        self.file_name = None;
+        Some(result.content)
+    }
+}
+
+fn load_entry(entry: &FileEntry, check_binary: bool, progress: &Mutex<LoadingProgress>, is_current: impl Fn() -> bool) -> anyhow::Result<LoadedContent> {
+    let data = entry.get_data(|_, data| data.to_vec())?;
+    if !is_current() {
+        return Err(anyhow::anyhow!("Loading cancelled"));
+    }
+    if check_binary && is_binary(&data) {
+        return Ok(LoadedContent::Binary);
+    }
+
+    *progress.lock().unwrap() = LoadingProgress::Parsing;
+    if get_extension(&entry.file_info.path) == "rip" {
+        let parser = parse_rip(&entry.file_info.path, &data);
+        Ok(LoadedContent::RipImage(rip_to_color_image(&parser)))
+    } else {
+        Ok(LoadedContent::Buffer(Buffer::from_bytes(&entry.file_info.path, true, &data)?))
+    }
+}
+
+/// Zero or 0xFF bytes at the start are unlikely in text based art formats.
+fn is_binary(data: &[u8]) -> bool {
+    data.iter().take(500).any(|&b| b == 0 || b == 255)
+}
@@ -5,25 +5,55 @@ use eframe::{
 };
 
 use i18n_embed_fl::fl;
-use icy_engine::{parse_with_parser, rip, Buffer};
-use icy_engine_gui::{animations::Animator, BufferView, MonitorSettings};
+use icy_engine_gui::{animations::Animator, BufferView, MonitorSettings, TerminalCalc};
 
 use std::{
     env::current_dir,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::Duration,
 };
 
 use self::{
+    background_task::{BackgroundTask, TaskKind},
+    convert::ConvertOptions,
+    export::ExportFormat,
+    file_operations::{FileOperation, FileOperationRequest},
     file_view::{FileEntry, FileView, Message},
-    options::{Options, ScrollSpeed},
+    find_bar::{FindBar, FindMessage},
+    keymap::Action,
+    options::{Options, ScaleMode, ScrollSpeed, SlideshowOptions, ViewMode},
+    playback::Playback,
+    slideshow::Slideshow,
 };
 
+mod archive;
+mod background_task;
+mod buffer_menu;
+pub mod convert;
+mod convert_dialog;
+mod export;
+mod file_filter;
+mod file_operation_dialog;
+mod file_operations;
 mod file_view;
+mod find_bar;
+mod folder_listing;
 mod help_dialog;
+mod history;
+mod keymap;
+pub mod loader;
+mod monitor_dialog;
 pub mod options;
+mod playback;
+mod quick_jump;
 mod sauce_dialog;
+mod sauce_loader;
+mod sauce_writer;
+mod search;
+mod slideshow;
+mod thumbnails;
+mod watcher;
 
 pub struct MainWindow<'a> {
     buffer_view: Arc<eframe::epaint::mutex::Mutex<BufferView>>,
@@ -45,6 +75,10 @@ pub struct MainWindow<'a> {
 
     sauce_dialog: Option<sauce_dialog::SauceDialog>,
     help_dialog: Option<help_dialog::HelpDialog>,
+    monitor_dialog: Option<monitor_dialog::MonitorDialog>,
+    convert_dialog: Option<convert_dialog::ConvertDialog>,
+    quick_jump: Option<quick_jump::QuickJump>,
+    file_operation_dialog: Option<file_operation_dialog::FileOperationDialog>,
 
     toasts: egui_notify::Toasts,
     is_closed: bool,
@@ -52,19 +86,44 @@ pub struct MainWindow<'a> {
     pub store_options: bool,
     // animations
     animation: Option<Arc<Mutex<Animator>>>,
+    loading_worker: loader::LoadingWorker,
+    find_bar: Option<FindBar>,
+    slideshow: Option<Slideshow>,
+    /// Options of the running slideshow if they were given on the command line, they are never stored.
+    slideshow_options: Option<SlideshowOptions>,
+    playback: Option<Playback>,
+    /// Running GIF export or folder conversion.
+    background_task: Option<BackgroundTask>,
+    /// Scale of the last shown frame, zooming starts from it.
+    scale: f32,
+    /// Scroll position a reloaded file is shown at once it has been loaded.
+    reload_scroll_pos: Option<f32>,
+    /// egui caches images by uri, a reloaded image has to be dropped from the cache before it's painted.
+    stale_image_uri: Option<String>,
 }
-const EXT_WHITE_LIST: [&str; 5] = ["seq", "diz", "nfo", "ice", "bbs"];
-
-const EXT_BLACK_LIST: [&str; 8] = ["zip", "rar", "gz", "tar", "7z", "pdf", "exe", "com"];
+/// Factor for a single zoom in/out step.
+const ZOOM_STEP: f32 = 1.25;
+const EXT_BLACK_LIST: [&str; 12] = ["zip", "rar", "gz", "tgz", "tar", "7z", "lha", "lzh", "arj", "pdf", "exe", "com"];
 
 impl<'a> App for MainWindow<'a> {
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        self.handle_find_keys(ctx);
+        self.handle_zoom_keys(ctx);
         egui::SidePanel::left("bottom_panel")
             .default_width(ctx.available_rect().width() * 3.0 / 2.0)
-            .exact_width(250.0)
+            .exact_width(self.get_side_panel_width())
             .resizable(true)
             .show(ctx, |ui| {
-                ui.set_enabled(self.sauce_dialog.is_none() && self.help_dialog.is_none());
+                if let Some(task) = &self.background_task {
+                    show_task_progress(ui, task);
+                }
+                ui.set_enabled(
+                    self.sauce_dialog.is_none()
+                        && self.help_dialog.is_none()
+                        && self.convert_dialog.is_none()
+                        && self.quick_jump.is_none()
+                        && self.file_operation_dialog.is_none(),
+                );
                 let command = self.file_view.show_ui(ui, false);
                 self.handle_command(command);
             });
@@ -73,7 +132,13 @@ impl<'a> App for MainWindow<'a> {
             .inner_margin(Margin::same(0.0))
             .fill(Color32::BLACK);
         egui::CentralPanel::default().frame(frame_no_margins).show(ctx, |ui| {
-            ui.set_enabled(self.sauce_dialog.is_none() && self.help_dialog.is_none());
+            ui.set_enabled(
+                self.sauce_dialog.is_none()
+                    && self.help_dialog.is_none()
+                    && self.convert_dialog.is_none()
+                    && self.quick_jump.is_none()
+                    && self.file_operation_dialog.is_none(),
+            );
             self.paint_main_area(ui)
         });
         self.in_scroll &= self.file_view.options.auto_scroll_enabled;
@@ -83,6 +148,8 @@ impl<'a> App for MainWindow<'a> {
         } else {
             ctx.request_repaint_after(Duration::from_millis(150));
         }
+        self.update_slideshow();
+        self.update_background_task(ctx);
 
         if let Some(sauce_dialog) = &mut self.sauce_dialog {
             if let Some(message) = sauce_dialog.show(ctx) {
@@ -90,12 +157,20 @@ impl<'a> App for MainWindow<'a> {
                     sauce_dialog::Message::CloseDialog => {
                         self.sauce_dialog = None;
                     }
+                    sauce_dialog::Message::Save(file, edit) => {
+                        self.sauce_dialog = None;
+                        self.write_sauce(&file, Some(&edit));
+                    }
+                    sauce_dialog::Message::Remove(file) => {
+                        self.sauce_dialog = None;
+                        self.write_sauce(&file, None);
+                    }
                 }
             }
         }
 
         if let Some(help_dialog) = &mut self.help_dialog {
-            if let Some(message) = help_dialog.show(ctx) {
+            if let Some(message) = help_dialog.show(ctx, &self.file_view.keymap) {
                 match message {
                     help_dialog::Message::CloseDialog => {
                         self.help_dialog = None;
@@ -104,25 +179,64 @@ impl<'a> App for MainWindow<'a> {
             }
         }
 
+        if let Some(convert_dialog) = &mut self.convert_dialog {
+            if let Some(message) = convert_dialog.show(ctx) {
+                match message {
+                    convert_dialog::Message::CloseDialog => {
+                        self.convert_dialog = None;
+                    }
+                    convert_dialog::Message::Convert {
+                        extension,
+                        options,
+                        convert_folder,
+                    } => {
+                        self.convert_dialog = None;
+                        self.convert(&extension, &options, convert_folder);
+                    }
+                }
+            }
+        }
+
+        if let Some(file_operation_dialog) = &mut self.file_operation_dialog {
+            if let Some(message) = file_operation_dialog.show(ctx) {
+                self.file_operation_dialog = None;
+                if let file_operation_dialog::Message::Run(request) = message {
+                    self.run_file_operation(request);
+                }
+            }
+        }
+
+        if let Some(quick_jump) = &mut self.quick_jump {
+            if let Some(message) = quick_jump.show(ctx, &self.file_view.options) {
+                self.quick_jump = None;
+                if let quick_jump::Message::Jump(path, selected_file) = message {
+                    self.handle_command(Some(Message::Jump(path, selected_file)));
+                }
+            }
+        }
+
+        if let Some(monitor_dialog) = &mut self.monitor_dialog {
+            if let Some(message) = monitor_dialog.show(ctx, &mut self.file_view.options.monitor) {
+                match message {
+                    monitor_dialog::Message::CloseDialog => {
+                        self.monitor_dialog = None;
+                    }
+                }
+            }
+        }
+
         self.toasts.show(ctx);
 
-        if ctx.input(|i| i.key_pressed(egui::Key::F11) || i.key_pressed(egui::Key::Enter) && i.modifiers.alt) {
+        if self.file_view.keymap.pressed(ctx, Action::ToggleFullScreen) {
             self.full_screen_mode = !self.full_screen_mode;
             ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.full_screen_mode));
         }
 
-        if ctx.input(|i| i.key_pressed(egui::Key::Q) && i.modifiers.alt) {
+        let close_pressed = self.file_view.keymap.pressed(ctx, Action::CloseDialog);
+        // a key bound to both, like Escape, quits only when no dialog was open
+        if !(close_pressed && self.close_dialog()) && self.file_view.keymap.pressed(ctx, Action::Quit) {
             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
         }
-        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            if self.sauce_dialog.is_some() {
-                self.sauce_dialog = None;
-            } else if self.help_dialog.is_some() {
-                self.help_dialog = None;
-            } else {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-            }
-        }
     }
 
     fn on_exit(&mut self, _gl: Option<&glow::Context>) {
@@ -130,6 +244,7 @@ impl<'a> App for MainWindow<'a> {
         if self.store_options {
             self.file_view.options.store_options();
         }
+        archive::remove_temp_files();
     }
 }
 
@@ -159,6 +274,12 @@ impl<'a> MainWindow<'a> {
             loaded_buffer: false,
             sauce_dialog: None,
             help_dialog: None,
+            monitor_dialog: None,
+            convert_dialog: None,
+            quick_jump: None,
+            file_operation_dialog: None,
+            reload_scroll_pos: None,
+            stale_image_uri: None,
             drag_started: false,
             cur_scroll_pos: 0.0,
             drag_vel: 0.0,
@@ -168,6 +289,13 @@ impl<'a> MainWindow<'a> {
             opened_file: None,
             is_closed: false,
             animation: None,
+            loading_worker: loader::LoadingWorker::new(),
+            find_bar: None,
+            slideshow: None,
+            slideshow_options: None,
+            playback: None,
+            background_task: None,
+            scale: 1.0,
             store_options: false,
         }
     }
@@ -187,13 +315,156 @@ impl<'a> MainWindow<'a> {
         self.last_scroll_pos = 1.0;
         self.opened_file = None;
         self.animation = None;
+        self.find_bar = None;
+        self.playback = None;
+        self.cancel_loading();
+    }
+
+    /// Closes the topmost dialog, returns false if none was open.
+    fn close_dialog(&mut self) -> bool {
+        if self.quick_jump.is_some() {
+            self.quick_jump = None;
+        } else if self.file_operation_dialog.is_some() {
+            self.file_operation_dialog = None;
+        } else if self.sauce_dialog.is_some() {
+            self.sauce_dialog = None;
+        } else if self.convert_dialog.is_some() {
+            self.convert_dialog = None;
+        } else if self.find_bar.is_some() {
+            self.find_bar = None;
+        } else if self.slideshow.is_some() {
+            self.stop_slideshow();
+        } else if self.help_dialog.is_some() {
+            self.help_dialog = None;
+        } else if self.monitor_dialog.is_some() {
+            self.monitor_dialog = None;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Find opens the find bar, the find next/previous keys are taken from the file view while it's open.
+    fn handle_find_keys(&mut self, ctx: &Context) {
+        let keymap = &self.file_view.keymap;
+        if keymap.consume(ctx, Action::Find) && self.loaded_buffer {
+            match &mut self.find_bar {
+                Some(find_bar) => find_bar.request_focus(),
+                None => self.find_bar = Some(FindBar::new()),
+            }
+        }
+        if let Some(find_bar) = &mut self.find_bar {
+            if keymap.consume(ctx, Action::FindPrevious) {
+                find_bar.set_pending_message(FindMessage::Previous);
+            } else if keymap.consume(ctx, Action::FindNext) {
+                find_bar.set_pending_message(FindMessage::Next);
+            }
+        }
+    }
+
+    /// Zooms the buffer view, resetting shows it at the original size.
+    fn handle_zoom_keys(&mut self, ctx: &Context) {
+        if !self.loaded_buffer && self.animation.is_none() {
+            return;
+        }
+        if self.file_view.keymap.consume(ctx, Action::ZoomIn) {
+            self.zoom_in();
+        }
+        if self.file_view.keymap.consume(ctx, Action::ZoomOut) {
+            self.zoom_out();
+        }
+        if self.file_view.keymap.consume(ctx, Action::ZoomReset) {
+            self.file_view.options.set_zoom(1.0);
+        }
+    }
+
+    fn get_zoom(&self) -> f32 {
+        if self.file_view.options.scale_mode == ScaleMode::Zoom {
+            self.file_view.options.zoom
+        } else {
+            self.scale
+        }
+    }
+
+    fn zoom_in(&mut self) {
+        let zoom = self.get_zoom();
+        let zoom = if self.file_view.options.integer_scaling {
+            zoom.floor() + 1.0
+        } else {
+            zoom * ZOOM_STEP
+        };
+        self.file_view.options.set_zoom(zoom);
+    }
+
+    fn zoom_out(&mut self) {
+        let zoom = self.get_zoom();
+        let zoom = if self.file_view.options.integer_scaling {
+            (zoom.floor() - 1.0).max(1.0)
+        } else {
+            zoom / ZOOM_STEP
+        };
+        self.file_view.options.set_zoom(zoom);
+    }
+
+    /// Computes the scale of the buffer view from the scale mode of the options.
+    fn get_scale(&self, available_size: Vec2) -> Vec2 {
+        let buffer_view = self.buffer_view.lock();
+        let buf = buffer_view.get_buffer();
+        let font_size = buf.get_font_dimensions();
+        let char_width = font_size.width + if buf.use_letter_spacing() { 1 } else { 0 };
+        let aspect_ratio = if buf.use_aspect_ratio() { loader::ASPECT_RATIO_SCALE } else { 1.0 };
+        let width = (buf.get_width() * char_width).max(1) as f32;
+        let height = (buf.get_line_count() * font_size.height).max(1) as f32 * aspect_ratio;
+
+        let options = &self.file_view.options;
+        let mut scale = match options.scale_mode {
+            ScaleMode::FitWidth => (available_size.x / width).min(2.0),
+            ScaleMode::FitPage => (available_size.x / width).min(available_size.y / height),
+            ScaleMode::Zoom => options.zoom,
+        };
+        if options.integer_scaling {
+            scale = scale.floor().max(1.0);
+        }
+        Vec2::new(scale, scale * aspect_ratio)
+    }
+
+    fn handle_find_message(&mut self, message: Option<FindMessage>, calc: &TerminalCalc) {
+        let Some(find_bar) = &mut self.find_bar else {
+            return;
+        };
+        let first_line = (calc.char_scroll_position.y / calc.char_size.y.max(1.0)) as i32;
+        let found = match message {
+            Some(FindMessage::Changed) => find_bar.search(self.buffer_view.lock().get_buffer(), first_line),
+            Some(FindMessage::Next) => find_bar.next(),
+            Some(FindMessage::Previous) => find_bar.previous(),
+            Some(FindMessage::Close) => {
+                self.find_bar = None;
+                return;
+            }
+            None => {
+                if find_bar.needs_search() {
+                    find_bar.search(self.buffer_view.lock().get_buffer(), first_line);
+                }
+                return;
+            }
+        };
+        if let Some(found) = found {
+            // show the match in the upper third of the view
+            self.cur_scroll_pos = (found.y as f32 * calc.char_size.y - calc.buffer_rect.height() / 3.0).max(0.0);
+        }
+    }
+
+    fn cancel_loading(&mut self) {
+        self.loading_worker.cancel();
     }
 
     pub fn show_file_chooser(&mut self, ctx: &Context) -> bool {
         self.is_closed = false;
+        self.handle_find_keys(ctx);
+        self.handle_zoom_keys(ctx);
         egui::SidePanel::left("bottom_panel")
             .default_width(ctx.available_rect().width() * 3.0 / 2.0)
-            .exact_width(250.0)
+            .exact_width(self.get_side_panel_width())
             .resizable(true)
             .show(ctx, |ui| {
                 let command = self.file_view.show_ui(ui, true);
@@ -201,8 +472,8 @@ impl<'a> MainWindow<'a> {
             });
 
         let frame_no_margins = egui::containers::Frame::none()
-                .outer_margin(Margin::same(0.0))
-                .inner_margin(Margin::same(0.0))
+            .outer_margin(Margin::same(0.0))
+            .inner_margin(Margin::same(0.0))
             .fill(Color32::BLACK);
         egui::CentralPanel::default().frame(frame_no_margins).show(ctx, |ui| self.paint_main_area(ui));
         self.in_scroll &= self.file_view.options.auto_scroll_enabled;
@@ -217,36 +488,392 @@ impl<'a> MainWindow<'a> {
         self.is_closed
     }
 
+    /// Starts a slideshow of the current folder, `options` overrides the stored slideshow options for this run.
+    pub fn start_slideshow(&mut self, options: Option<SlideshowOptions>) {
+        self.slideshow_options = options;
+        let options = self.slideshow_options.as_ref().unwrap_or(&self.file_view.options.slideshow);
+        let slideshow = Slideshow::new(&self.file_view, options);
+        let Some(first) = slideshow.get_current().cloned() else {
+            self.toasts
+                .info(fl!(crate::LANGUAGE_LOADER, "toast-slideshow-empty"))
+                .set_duration(Some(Duration::from_secs(3)));
+            return;
+        };
+        self.slideshow = Some(slideshow);
+        self.file_view.slideshow_running = true;
+        self.show_slideshow_file(first);
+    }
+
+    pub fn stop_slideshow(&mut self) {
+        self.slideshow = None;
+        self.slideshow_options = None;
+        self.file_view.slideshow_running = false;
+    }
+
+    fn update_slideshow(&mut self) {
+        let Some(slideshow) = &mut self.slideshow else {
+            return;
+        };
+        if self.loading_worker.is_loading() {
+            slideshow.restart_timer();
+            return;
+        }
+        let options = self.slideshow_options.as_ref().unwrap_or(&self.file_view.options.slideshow);
+        let busy = self.in_scroll || self.playback.as_ref().map_or(false, |playback| !playback.is_finished());
+        if !slideshow.should_advance(options, busy) {
+            return;
+        }
+        let next = slideshow.next(options).cloned();
+        match next {
+            Some(path) => self.show_slideshow_file(path),
+            None => {
+                self.stop_slideshow();
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-slideshow-finished"))
+                    .set_duration(Some(Duration::from_secs(3)));
+            }
+        }
+    }
+
+    /// Selects a playlist file, changing to its folder if it's not in the shown one.
+    fn show_slideshow_file(&mut self, path: PathBuf) {
+        if let Some(file) = self.file_view.files.iter().position(|entry| entry.file_info.path == path) {
+            self.handle_command(Some(Message::Select(file, false)));
+            return;
+        }
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(file_name)) => {
+                // the file is selected once the folder listing reaches it
+                self.reset_state();
+                let command = self.file_view.jump_to(parent.to_path_buf(), Some(file_name.to_string_lossy().to_string()));
+                self.handle_command(command);
+            }
+            _ => log::warn!("Slideshow file not found: {}", path.display()),
+        }
+    }
+
+    /// Replays the selected file at the baud rate of the options.
+    fn start_playback(&mut self) {
+        let Some(entry) = self.file_view.selected_file.and_then(|file| self.file_view.files.get(file)) else {
+            return;
+        };
+        if !self.loaded_buffer || !playback::is_supported(&entry.file_info.path) {
+            self.toasts
+                .info(fl!(crate::LANGUAGE_LOADER, "toast-playback-not-supported"))
+                .set_duration(Some(Duration::from_secs(3)));
+            return;
+        }
+        let mut playback = match entry.get_data(|path, data| Playback::new(path, data)) {
+            Ok(playback) => playback,
+            Err(err) => {
+                log::error!("Error reading file for playback: {err}");
+                return;
+            }
+        };
+        if let Err(err) = playback.restart(&mut self.buffer_view.lock()) {
+            log::error!("Error starting playback: {err}");
+            return;
+        }
+        self.in_scroll = false;
+        self.cur_scroll_pos = 0.0;
+        self.playback = Some(playback);
+    }
+
+    /// Ends the playback and shows the whole file again.
+    fn stop_playback(&mut self) {
+        if self.playback.take().is_some() {
+            if let Some(file) = self.file_view.selected_file {
+                self.handle_command(Some(Message::Select(file, true)));
+            }
+        }
+    }
+
+    fn export(&mut self, format: ExportFormat) {
+        match self.run_export(format) {
+            Ok(true) => self.show_task_result(TaskKind::Export, Ok(1), 0),
+            Ok(false) => {}
+            Err(err) => self.show_task_result(TaskKind::Export, Err(err), 0),
+        }
+    }
+
+    /// Returns false if the user cancelled the file dialog or the export runs in the background.
+    fn run_export(&mut self, format: ExportFormat) -> anyhow::Result<bool> {
+        match format {
+            ExportFormat::Png | ExportFormat::Png2x => {
+                let scale = if format == ExportFormat::Png2x { 2 } else { 1 };
+                let img = self.render_current(scale)?;
+                let Some(path) = self.pick_export_file("png") else {
+                    return Ok(false);
+                };
+                export::save_png(&img, &path)?;
+            }
+            ExportFormat::Clipboard => {
+                let img = self.render_current(1)?;
+                export::copy_to_clipboard(&img)?;
+            }
+            ExportFormat::Gif => {
+                self.check_no_background_task()?;
+                if let Some(anim) = self.animation.clone() {
+                    let Some(path) = self.pick_export_file("gif") else {
+                        return Ok(false);
+                    };
+                    self.background_task = Some(BackgroundTask::start(TaskKind::Export, move |progress| {
+                        export::save_animation_gif(&anim, &path, progress)?;
+                        Ok(1)
+                    }));
+                    return Ok(false);
+                }
+                let Some(entry) = self.file_view.selected_file.and_then(|file| self.file_view.files.get(file)) else {
+                    return Err(anyhow::anyhow!(fl!(crate::LANGUAGE_LOADER, "error-nothing-to-export")));
+                };
+                if !self.loaded_buffer || !playback::is_supported(&entry.file_info.path) {
+                    return Err(anyhow::anyhow!(fl!(crate::LANGUAGE_LOADER, "error-gif-not-supported")));
+                }
+                let (file, data) = entry.get_data(|path, data| (path.clone(), data.to_vec()))?;
+                let Some(path) = self.pick_export_file("gif") else {
+                    return Ok(false);
+                };
+                let baud_rate = self.file_view.options.baud_rate;
+                self.background_task = Some(BackgroundTask::start(TaskKind::Export, move |progress| {
+                    export::save_playback_gif(&file, &data, baud_rate, &path, progress)?;
+                    Ok(1)
+                }));
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn convert(&mut self, extension: &str, options: &ConvertOptions, convert_folder: bool) {
+        let result = if convert_folder {
+            self.convert_folder(extension, options)
+        } else {
+            self.convert_buffer(extension, options)
+        };
+        match result {
+            Ok(Some(count)) => self.show_task_result(TaskKind::Convert, Ok(count), 0),
+            Ok(None) => {}
+            Err(err) => self.show_task_result(TaskKind::Convert, Err(err), 0),
+        }
+    }
+
+    /// Saves the shown buffer, returns `None` if the user cancelled the file dialog.
+    fn convert_buffer(&self, extension: &str, options: &ConvertOptions) -> anyhow::Result<Option<usize>> {
+        let Some(path) = self.pick_export_file(extension) else {
+            return Ok(None);
+        };
+        let bytes = convert::convert_buffer(self.buffer_view.lock().get_buffer(), extension, options)?;
+        std::fs::write(path, bytes)?;
+        Ok(Some(1))
+    }
+
+    /// Converts all files of the shown folder the engine can load in the background, files that fail are logged and skipped.
+    /// Returns `None`, the number of converted files is shown once the task is done.
+    fn convert_folder(&mut self, extension: &str, options: &ConvertOptions) -> anyhow::Result<Option<usize>> {
+        self.check_no_background_task()?;
+        let Some(output_dir) = rfd::FileDialog::new().set_directory(self.file_view.get_path()).pick_folder() else {
+            return Ok(None);
+        };
+        let entries: Vec<FileEntry> = self
+            .file_view
+            .files
+            .iter()
+            .filter(|entry| entry.is_file() && loader::is_engine_format(&loader::get_extension(&entry.file_info.path)))
+            .cloned()
+            .collect();
+        let extension = extension.to_string();
+        let options = options.clone();
+        self.background_task = Some(BackgroundTask::start(TaskKind::Convert, move |progress| {
+            progress.set_total(entries.len());
+            let jobs: Vec<(PathBuf, PathBuf)> = entries
+                .iter()
+                .map(|entry| {
+                    let output = convert::get_output_path(&entry.file_info.path, Some(&output_dir), &extension);
+                    (entry.file_info.path.clone(), output)
+                })
+                .collect();
+            // existing files are never overwritten, the folder dialog doesn't ask
+            let conflicts = convert::get_output_conflicts(&jobs, false);
+            let mut converted = 0;
+            for ((entry, (_, output)), conflict) in entries.iter().zip(&jobs).zip(conflicts) {
+                progress.check_cancelled()?;
+                let result = match conflict {
+                    Some(conflict) => Err(anyhow::anyhow!(conflict)),
+                    None => convert_entry(entry, output, &extension, &options),
+                };
+                match result {
+                    Ok(()) => converted += 1,
+                    Err(err) => {
+                        log::error!("Skipped converting {}: {err}", entry.file_info.path.display());
+                        progress.skip();
+                    }
+                }
+                progress.advance();
+            }
+            Ok(converted)
+        }));
+        Ok(None)
+    }
+
+    fn check_no_background_task(&self) -> anyhow::Result<()> {
+        if self.background_task.is_some() {
+            return Err(anyhow::anyhow!(fl!(crate::LANGUAGE_LOADER, "error-task-running")));
+        }
+        Ok(())
+    }
+
+    /// Shows the result of the background task once it's done.
+    fn update_background_task(&mut self, ctx: &Context) {
+        let Some(task) = &mut self.background_task else {
+            return;
+        };
+        if !task.is_finished() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+            return;
+        }
+        let kind = task.kind;
+        let skipped = task.get_progress().get_skipped();
+        let result = task.take_result();
+        self.background_task = None;
+        match result {
+            Some(result) => self.show_task_result(kind, result, skipped),
+            None => {
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-task-cancelled"))
+                    .set_duration(Some(Duration::from_secs(3)));
+            }
+        }
+    }
+
+    /// The result holds the number of written files, `skipped` files failed or had conflicting outputs.
+    fn show_task_result(&mut self, kind: TaskKind, result: anyhow::Result<usize>, skipped: usize) {
+        match (kind, result) {
+            (TaskKind::Export, Ok(_)) => {
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-export-done"))
+                    .set_duration(Some(Duration::from_secs(3)));
+            }
+            (TaskKind::Convert, Ok(count)) if skipped > 0 => {
+                self.toasts
+                    .warning(fl!(crate::LANGUAGE_LOADER, "toast-converted-skipped", count = count, skipped = skipped))
+                    .set_duration(Some(Duration::from_secs(5)));
+            }
+            (TaskKind::Convert, Ok(count)) => {
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-converted", count = count))
+                    .set_duration(Some(Duration::from_secs(3)));
+            }
+            (TaskKind::Export, Err(err)) => {
+                log::error!("Error exporting: {err}");
+                self.toasts
+                    .error(fl!(crate::LANGUAGE_LOADER, "toast-export-error", error = err.to_string()))
+                    .set_duration(Some(Duration::from_secs(5)));
+            }
+            (TaskKind::Convert, Err(err)) => {
+                log::error!("Error converting: {err}");
+                self.toasts
+                    .error(fl!(crate::LANGUAGE_LOADER, "toast-convert-error", error = err.to_string()))
+                    .set_duration(Some(Duration::from_secs(5)));
+            }
+        }
+    }
+
+    /// Renders the shown buffer or RIP screen.
+    fn render_current(&self, scale: u32) -> anyhow::Result<image::RgbaImage> {
+        let img = if let Some(color_image) = &self.texture_handle {
+            export::color_image_to_rgba(color_image)?
+        } else if self.loaded_buffer || self.animation.is_some() {
+            loader::render_buffer(self.buffer_view.lock().get_buffer())?
+        } else {
+            return Err(anyhow::anyhow!(fl!(crate::LANGUAGE_LOADER, "error-nothing-to-export")));
+        };
+        Ok(export::scale_image(img, scale))
+    }
+
+    fn pick_export_file(&self, extension: &str) -> Option<PathBuf> {
+        let file_name = self
+            .file_view
+            .selected_file
+            .and_then(|file| self.file_view.files.get(file))
+            .and_then(|entry| entry.file_info.path.file_stem())
+            .map(|stem| format!("{}.{extension}", stem.to_string_lossy()))
+            .unwrap_or_else(|| format!("export.{extension}"));
+        rfd::FileDialog::new()
+            .set_file_name(file_name)
+            .add_filter(extension.to_ascii_uppercase(), &[extension])
+            .save_file()
+    }
+
+    /// Keeps the caret visible while the playback is running.
+    fn follow_playback_caret(&mut self, calc: &TerminalCalc) {
+        let Some(playback) = &self.playback else {
+            return;
+        };
+        if playback.paused || playback.is_finished() {
+            return;
+        }
+        let caret_y = self.buffer_view.lock().get_caret().get_position().y as f32 * calc.char_size.y;
+        let view_height = calc.buffer_rect.height();
+        if caret_y + calc.char_size.y > self.cur_scroll_pos + view_height {
+            self.cur_scroll_pos = caret_y + calc.char_size.y - view_height;
+        } else if caret_y < self.cur_scroll_pos {
+            self.cur_scroll_pos = caret_y;
+        }
+    }
+
+    fn get_side_panel_width(&self) -> f32 {
+        match self.file_view.options.view_mode {
+            ViewMode::List => 250.0,
+            // room for four thumbnail columns
+            ViewMode::Grid => 4.0 * file_view::GRID_CELL_WIDTH + 24.0,
+            ViewMode::Table => 760.0,
+        }
+    }
+
     fn paint_main_area(&mut self, ui: &mut egui::Ui) {
+        if let Some(uri) = self.stale_image_uri.take() {
+            ui.ctx().forget_image(&uri);
+        }
         if let Some(err) = &self.error_text {
             ui.colored_label(ui.style().visuals.error_fg_color, err);
             return;
         }
-        /*
-        if let Some(image_loading_thread) = &self.image_loading_thread {
-            if image_loading_thread.is_finished() {
-                if let Some(img) = self.image_loading_thread.take() {
-                    match img.join() {
-                        Ok(img) => match img {
-                            Ok(img) => {
-                                self.retained_image = Some(img);
-                            }
-                            Err(err) => {
-                                self.error_text = Some(err.to_string());
-                            }
-                        },
-                        Err(err) => {
-                            self.error_text = Some(format!("{err:?}"));
+        if self.loading_worker.is_loading() {
+            if let Some(result) = self.loading_worker.poll() {
+                match result {
+                    Ok(loader::LoadedContent::Buffer(buf)) => {
+                        self.buffer_view.lock().set_buffer(buf);
+                        self.loaded_buffer = true;
+                        match self.reload_scroll_pos.take() {
+                            Some(scroll_pos) => self.cur_scroll_pos = scroll_pos,
+                            None => self.in_scroll = true,
                         }
                     }
-                } else {
-                    self.error_text = Some(fl!(crate::LANGUAGE_LOADER, "error-never-happens").to_string());
+                    Ok(loader::LoadedContent::RipImage(img)) => {
+                        self.texture_handle = Some(img);
+                    }
+                    // shows the button to load it anyways
+                    Ok(loader::LoadedContent::Binary) => {}
+                    Err(err) => {
+                        self.error_text = Some(err.to_string());
+                    }
                 }
             } else {
-                ui.centered_and_justified(|ui| ui.heading(fl!(crate::LANGUAGE_LOADER, "message-loading-image")));
+                let name = self.loading_worker.get_file_name().to_string();
+                let text = match self.loading_worker.get_progress() {
+                    loader::LoadingProgress::Reading => fl!(crate::LANGUAGE_LOADER, "message-reading-file", name = name),
+                    loader::LoadingProgress::Parsing => fl!(crate::LANGUAGE_LOADER, "message-parsing-file", name = name),
+                };
+                ui.add_space(ui.available_height() / 3.0);
+                ui.vertical_centered(|ui| {
+                    ui.spinner();
+                    ui.add_space(8.0);
+                    ui.heading(text);
+                });
+                ui.ctx().request_repaint_after(Duration::from_millis(50));
+                return;
             }
-            return;
-        } */
+        }
 
         if let Some(img) = &self.retained_image {
             ScrollArea::both().show(ui, |ui| {
@@ -278,7 +905,31 @@ impl<'a> MainWindow<'a> {
         }
 
         if self.loaded_buffer {
-            let (response, calc) = self.show_buffer_view(ui, MonitorSettings::default());
+            let mut find_message = None;
+            if let Some(find_bar) = &mut self.find_bar {
+                egui::TopBottomPanel::top("find_bar").show_inside(ui, |ui| {
+                    find_message = find_bar.show(ui, &self.file_view.keymap);
+                });
+                // searching pauses auto scroll
+                self.in_scroll = false;
+            }
+            let mut close_playback = false;
+            if let Some(playback) = &mut self.playback {
+                egui::TopBottomPanel::bottom("playback_bar").show_inside(ui, |ui| {
+                    close_playback = playback.show_controls(ui, &mut self.buffer_view.lock(), &mut self.file_view.options.baud_rate);
+                });
+                let dt = ui.input(|i| i.unstable_dt);
+                playback.update(&mut self.buffer_view.lock(), dt, self.file_view.options.baud_rate);
+                self.in_scroll = false;
+                if !playback.paused && !playback.is_finished() {
+                    ui.ctx().request_repaint();
+                }
+            }
+            if close_playback {
+                self.stop_playback();
+                return;
+            }
+            let (response, calc) = self.show_buffer_view(ui, self.file_view.options.monitor.get_monitor_settings());
 
             // stop scrolling when reached the end.
             if self.in_scroll {
@@ -288,33 +939,46 @@ impl<'a> MainWindow<'a> {
                 self.last_scroll_pos = calc.char_scroll_position.y;
             }
             self.cur_scroll_pos = calc.char_scroll_position.y;
+            self.follow_playback_caret(&calc);
+            self.handle_find_message(find_message, &calc);
+            if let Some(find_bar) = &self.find_bar {
+                find_bar.paint_matches(ui, &calc);
+            }
+            response.context_menu(|ui| {
+                if let Err(err) = buffer_menu::show_buffer_menu(ui, &mut self.buffer_view.lock()) {
+                    log::error!("Error changing the buffer: {err}");
+                    self.toasts
+                        .error(fl!(crate::LANGUAGE_LOADER, "toast-buffer-menu-error", error = err.to_string()))
+                        .set_duration(Some(Duration::from_secs(5)));
+                }
+            });
 
-            if ui.input(|i: &egui::InputState| i.key_pressed(egui::Key::Home) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollTop) {
                 self.cur_scroll_pos = 0.0;
                 self.in_scroll = false;
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::End) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollBottom) {
                 self.cur_scroll_pos = f32::MAX;
                 self.in_scroll = false;
             }
 
-            if ui.input(|i: &egui::InputState| i.key_pressed(egui::Key::ArrowUp) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollUp) {
                 self.key_vel = 500.0;
                 self.in_scroll = false;
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::ArrowDown) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollDown) {
                 self.key_vel -= 250.0;
                 self.in_scroll = false;
             }
 
-            if ui.input(|i: &egui::InputState| i.key_pressed(egui::Key::PageUp) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollPageUp) {
                 self.key_vel = 5000.0;
                 self.in_scroll = false;
             }
 
-            if ui.input(|i| i.key_pressed(egui::Key::PageDown) && i.modifiers.ctrl) {
+            if self.file_view.keymap.pressed(ui.ctx(), Action::ScrollPageDown) {
                 self.key_vel -= 2500.0;
                 self.in_scroll = false;
             }
@@ -391,13 +1055,8 @@ impl<'a> MainWindow<'a> {
     }
 
     fn show_buffer_view(&mut self, ui: &mut egui::Ui, monitor_settings: MonitorSettings) -> (egui::Response, icy_engine_gui::TerminalCalc) {
-        let w = (ui.available_width() / 8.0).floor();
-        let scalex = (w / self.buffer_view.lock().get_width() as f32).min(2.0);
-        let scaley = if self.buffer_view.lock().get_buffer_mut().use_aspect_ratio() {
-            scalex * 1.35
-        } else {
-            scalex
-        };
+        let scale = self.get_scale(ui.available_size());
+        self.scale = scale.x;
 
         let dt = ui.input(|i| i.unstable_dt);
         let sp = if self.in_scroll {
@@ -408,7 +1067,7 @@ impl<'a> MainWindow<'a> {
 
         let mut opt = icy_engine_gui::TerminalOptions {
             stick_to_bottom: false,
-            scale: Some(Vec2::new(scalex, scaley)),
+            scale: Some(scale),
             use_terminal_height: false,
             scroll_offset_y: Some(sp),
             monitor_settings,
@@ -432,30 +1091,24 @@ impl<'a> MainWindow<'a> {
         }
 
         let (response, calc) = icy_engine_gui::show_terminal_area(ui, self.buffer_view.clone(), opt);
+        if response.hovered() {
+            // Ctrl+mouse wheel
+            let zoom_delta = ui.input(|i| i.zoom_delta());
+            if zoom_delta != 1.0 {
+                self.file_view.options.set_zoom(self.get_zoom() * zoom_delta);
+            }
+        }
         (response, calc)
     }
 
     fn open_selected(&mut self, file: usize) -> bool {
-        if file >= self.file_view.files.len() {
+        if file >= self.file_view.files.len() || !self.file_view.files[file].is_dir_or_archive() {
             return false;
         }
 
-        let open_path = if self.file_view.files[file].is_file() {
-            if let Some(ext) = self.file_view.files[file].file_info.path.extension() {
-                ext == "zip"
-            } else {
-                false
-            }
-        } else {
-            true
-        };
-
-        if open_path {
-            self.reset_state();
-            self.file_view.set_path(self.file_view.files[file].file_info.path.clone());
-        }
-
-        open_path
+        self.file_view.remember_location();
+        self.reset_state();
+        self.file_view.open(file)
     }
 
     fn view_selected(&mut self, file: usize, force_load: bool) {
@@ -466,13 +1119,8 @@ impl<'a> MainWindow<'a> {
         self.last_scroll_pos = -1.0;
         let entry = &self.file_view.files[file];
         if entry.is_file() {
-            let ext = if let Some(ext) = entry.file_info.path.extension() {
-                let ext2 = ext.to_ascii_lowercase();
-                ext2.to_str().unwrap_or_default().to_string()
-            } else {
-                String::new()
-            };
-            if ext == "png" || ext == "jpg" || ext == "jpeg" || ext == "gif" || ext == "bmp" {
+            let ext = loader::get_extension(&entry.file_info.path);
+            if loader::is_image_extension(&ext) {
                 let image = entry
                     .read_image(|path: &PathBuf, data| {
                         let file_name = path.to_string_lossy().to_string();
@@ -489,21 +1137,10 @@ impl<'a> MainWindow<'a> {
                 return;
             }*/
             if ext == "icyanim" {
-                let anim = entry.get_data(|path, data| match String::from_utf8(data.to_vec()) {
-                    Ok(data) => {
-                        let parent = path.parent().map(|path| path.to_path_buf());
-                        let anim = Animator::run(&parent, data);
+                match entry.get_data(|path, data| loader::load_animation(path, data)) {
+                    Ok(Ok(anim)) => {
                         anim.lock().unwrap().set_is_loop(true);
                         anim.lock().unwrap().set_is_playing(true);
-                        Ok(anim)
-                    }
-                    Err(err) => {
-                        log::error!("Error while parsing icyanim file: {err}");
-                        Err(anyhow::anyhow!("Error while parsing icyanim file: {err}"))
-                    }
-                });
-                match anim {
-                    Ok(Ok(anim)) => {
                         anim.lock().unwrap().start_playback(self.buffer_view.clone());
                         self.animation = Some(anim);
                         return;
@@ -516,75 +1153,116 @@ impl<'a> MainWindow<'a> {
             }
 
             if ext == "rip" {
-                
-                match entry.get_data(|_path, data| {
-                    let mut rip_parser = rip::Parser::new(Box::default(), PathBuf::new());
-                    let mut result: Buffer = Buffer::new((80, 25));
-                    result.is_terminal_buffer = false;
-                        
-                    let (text, is_unicode) = icy_engine::convert_ansi_to_utf8(data);
-                    if is_unicode {
-                        result.buffer_type = icy_engine::BufferType::Unicode;
-                    }
-                    
-                    match parse_with_parser(&mut result, &mut rip_parser, &text, true) {
-                        Ok(_) => rip_parser,
-                        Err(err) => {
-                            log::error!("Error while parsing rip file: {err}");
-                            rip_parser
-                        }
-                    }
-                }) {
-                    Ok(buf) => {
-                        let size = buf.bgi.window;
-                        let mut pixels = Vec::new();
-                        let pal = buf.bgi.get_palette().clone();
-                        for i in buf.bgi.screen {
-                            let (r, g, b) = pal.get_rgb(i as u32);
-                            pixels.push(r);
-                            pixels.push(g);
-                            pixels.push(b);
-                            pixels.push(255);
-                        }
-                        let color_image: ColorImage = ColorImage::from_rgba_premultiplied([size.width as usize, size.height as usize], &pixels);
-                        self.texture_handle = Some(color_image);
-                    }
-                    Err(err) => self.error_text = Some(err.to_string()),
-                }
-
+                self.loading_worker.start(entry.clone(), false);
                 return;
             }
 
-            if force_load
-                || EXT_WHITE_LIST.contains(&ext.as_str())
-                || icy_engine::FORMATS.iter().any(|f| {
-                    let e = ext.as_str().to_ascii_lowercase();
-                    f.get_file_extension() == e || f.get_alt_extensions().contains(&e)
-                })
-                || !EXT_BLACK_LIST.contains(&ext.as_str()) && !is_binary(entry)
-            {
-                match entry.get_data(|path, data| Buffer::from_bytes(path, true, data)) {
-                    Ok(buf) => match buf {
-                        Ok(buf) => {
-                            self.buffer_view.lock().set_buffer(buf);
-                            self.loaded_buffer = true;
-                            self.in_scroll = true;
-                        }
-                        Err(err) => self.error_text = Some(err.to_string()),
-                    },
-                    Err(err) => self.error_text = Some(err.to_string()),
+            let known = force_load || loader::EXT_WHITE_LIST.contains(&ext.as_str()) || loader::is_engine_format(&ext);
+            if known || !EXT_BLACK_LIST.contains(&ext.as_str()) {
+                // reading the data may be slow, unknown files are checked on the loading thread
+                self.loading_worker.start(entry.clone(), !known);
+            }
+        }
+    }
+
+    /// Asks for the destination folder if the operation needs one and shows the confirmation.
+    fn start_file_operation(&mut self, operation: FileOperation) {
+        if !self.file_view.can_run(operation) {
+            return;
+        }
+        let files: Vec<PathBuf> = self.file_view.get_operation_files().iter().map(|entry| entry.file_info.path.clone()).collect();
+        let mut destination = None;
+        if operation.needs_destination() {
+            let mut dialog = rfd::FileDialog::new();
+            let path = self.file_view.get_path();
+            if path.is_dir() {
+                dialog = dialog.set_directory(path);
+            }
+            let Some(folder) = dialog.pick_folder() else {
+                return;
+            };
+            destination = Some(folder);
+        }
+        self.file_operation_dialog = Some(file_operation_dialog::FileOperationDialog::new(operation, files, destination));
+    }
+
+    fn run_file_operation(&mut self, request: FileOperationRequest) {
+        let FileOperationRequest { operation, files, destination } = request;
+        let selected_path = self
+            .file_view
+            .selected_file
+            .and_then(|file| self.file_view.files.get(file))
+            .map(|entry| entry.file_info.path.clone());
+
+        // returns the paths that were added or removed
+        let result = match (operation, &destination) {
+            (FileOperation::Copy, Some(dest)) => file_operations::copy_files(&files, dest),
+            (FileOperation::Move, Some(dest)) => file_operations::move_files(&files, dest).map(|targets| [files.clone(), targets].concat()),
+            (FileOperation::Rename, Some(new_path)) => file_operations::rename_file(&files[0], new_path).map(|()| vec![files[0].clone(), new_path.clone()]),
+            (FileOperation::Trash, _) => file_operations::trash_files(&files).map(|()| files.clone()),
+            (FileOperation::Extract, Some(dest)) => match self.file_view.get_archive_level() {
+                Some(level) => file_operations::extract_entries(&level.source, &level.folder, &files, dest),
+                None => file_operations::extract_archives(&files, dest),
+            },
+            _ => return,
+        };
+
+        match result {
+            Ok(changed_paths) => {
+                let command = self.file_view.update_after_operation(changed_paths);
+                self.handle_command(command);
+                if self.file_view.selected_file.is_none() {
+                    // a renamed file stays selected, a removed one is no longer shown
+                    let renamed = destination.filter(|_| operation == FileOperation::Rename && selected_path.as_ref() == files.first());
+                    let file = renamed.and_then(|path| self.file_view.files.iter().position(|entry| entry.file_info.path == path));
+                    match file {
+                        Some(file) => self.handle_command(Some(Message::Select(file, false))),
+                        None if selected_path.is_some() => self.reset_state(),
+                        None => {}
+                    }
                 }
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-file-operation-done"))
+                    .set_duration(Some(Duration::from_secs(3)));
+            }
+            Err(err) => {
+                log::error!("Error in file operation: {err}");
+                self.toasts
+                    .error(fl!(crate::LANGUAGE_LOADER, "toast-file-operation-error", error = err.to_string()))
+                    .set_duration(Some(Duration::from_secs(5)));
             }
         }
     }
 
+    /// Loads the selected file again after it changed on disk, the view stays at the same scroll position.
+    fn reload(&mut self, file: usize) {
+        if self.playback.is_some() || self.slideshow.is_some() {
+            return;
+        }
+        let scroll_pos = self.cur_scroll_pos;
+        let force_load = self.loaded_buffer;
+        self.stale_image_uri = self.retained_image.as_ref().and_then(|image| image.source().uri().map(str::to_string));
+        self.reset_state();
+        self.file_view.selected_file = Some(file);
+        self.view_selected(file, force_load);
+        if self.loading_worker.is_loading() {
+            self.reload_scroll_pos = Some(scroll_pos);
+        }
+    }
+
     fn reset_state(&mut self) {
+        self.cancel_loading();
+        if let Some(find_bar) = &mut self.find_bar {
+            find_bar.invalidate();
+        }
+        self.playback = None;
         self.retained_image = None;
         self.texture_handle = None;
         self.error_text = None;
         self.loaded_buffer = false;
         self.file_view.selected_file = None;
         self.cur_scroll_pos = 0.0;
+        self.reload_scroll_pos = None;
     }
 
     pub fn handle_command(&mut self, command: Option<Message>) {
@@ -596,6 +1274,9 @@ impl<'a> MainWindow<'a> {
                         if file < self.file_view.files.len() {
                             self.file_view.selected_file = Some(file);
                             self.file_view.scroll_pos = Some(file);
+                            if self.file_view.files[file].is_file() {
+                                self.file_view.remember_recent();
+                            }
                             self.view_selected(file, fore_load);
                         }
                     }
@@ -607,17 +1288,46 @@ impl<'a> MainWindow<'a> {
                 Message::Open(file) => {
                     self.is_closed = !self.open(file);
                 }
+                Message::Reload(file) => {
+                    self.reload(file);
+                }
                 Message::Cancel => {
                     self.is_closed = true;
                 }
                 Message::ParentFolder => {
-                    let mut p = self.file_view.get_path();
-                    if p.pop() {
+                    if self.file_view.can_go_up() {
+                        self.file_view.remember_location();
                         self.reset_state();
-                        self.file_view.set_path(p);
-                        self.handle_command(Some(Message::Select(0, false)));
+                        self.file_view.parent_folder();
+                        let command = self.file_view.select_first();
+                        self.handle_command(command);
                     }
                 }
+                Message::Back => {
+                    let location = self.file_view.history.back(self.file_view.get_location());
+                    if let Some(location) = location {
+                        self.reset_state();
+                        let command = self.file_view.restore_location(location);
+                        self.handle_command(command);
+                    }
+                }
+                Message::Forward => {
+                    let location = self.file_view.history.forward(self.file_view.get_location());
+                    if let Some(location) = location {
+                        self.reset_state();
+                        let command = self.file_view.restore_location(location);
+                        self.handle_command(command);
+                    }
+                }
+                Message::Jump(path, selected_file) => {
+                    self.file_view.remember_location();
+                    self.reset_state();
+                    let command = self.file_view.jump_to(path, selected_file);
+                    self.handle_command(command);
+                }
+                Message::ShowQuickJump => {
+                    self.quick_jump = Some(quick_jump::QuickJump::new());
+                }
                 Message::ToggleAutoScroll => {
                     self.file_view.options.auto_scroll_enabled = !self.file_view.options.auto_scroll_enabled;
                     self.in_scroll = self.file_view.options.auto_scroll_enabled;
@@ -635,10 +1345,48 @@ impl<'a> MainWindow<'a> {
                 Message::ShowSauce(file) => {
                     if file < self.file_view.files.len() {
                         if let Some(sauce) = self.file_view.files[file].get_sauce() {
-                            self.sauce_dialog = Some(sauce_dialog::SauceDialog::new(sauce));
+                            let writable = sauce_writer::check_writable(&self.file_view.files[file]);
+                            let path = self.file_view.files[file].file_info.path.clone();
+                            self.sauce_dialog = Some(sauce_dialog::SauceDialog::new(path, sauce, writable));
                         }
                     }
                 }
+                Message::StartSearch => {
+                    self.reset_state();
+                    self.file_view.start_search();
+                }
+                Message::ShowInFolder(file) => {
+                    self.file_view.remember_location();
+                    self.reset_state();
+                    let command = self.file_view.show_in_folder(file);
+                    self.handle_command(command);
+                }
+                Message::TogglePlayback => {
+                    if self.playback.is_some() {
+                        self.stop_playback();
+                    } else {
+                        self.start_playback();
+                    }
+                }
+                Message::ToggleSlideshow => {
+                    if self.slideshow.is_some() {
+                        self.stop_slideshow();
+                    } else {
+                        self.start_slideshow(None);
+                    }
+                }
+                Message::Export(format) => {
+                    self.export(format);
+                }
+                Message::ShowConvertDialog => {
+                    self.convert_dialog = Some(convert_dialog::ConvertDialog::new(self.loaded_buffer));
+                }
+                Message::FileOperation(operation) => {
+                    self.start_file_operation(operation);
+                }
+                Message::ShowMonitorSettings => {
+                    self.monitor_dialog = Some(monitor_dialog::MonitorDialog::new());
+                }
                 Message::ShowHelpDialog => {
                     self.help_dialog = Some(help_dialog::HelpDialog::new());
                 }
@@ -667,15 +1415,46 @@ impl<'a> MainWindow<'a> {
         }
     }
 
+    fn write_sauce(&mut self, path: &Path, edit: Option<&sauce_writer::SauceEdit>) {
+        let Some(file) = self.file_view.files.iter().position(|entry| entry.file_info.path == path) else {
+            return;
+        };
+        let entry = &mut self.file_view.files[file];
+        match sauce_writer::write_sauce(entry, edit) {
+            Ok(()) => {
+                entry.read_sauce = false;
+                entry.sauce = None;
+                entry.load_sauce();
+                self.file_view.invalidate_filter();
+                self.toasts
+                    .info(fl!(crate::LANGUAGE_LOADER, "toast-sauce-written"))
+                    .set_duration(Some(Duration::from_secs(3)));
+                if self.file_view.selected_file == Some(file) {
+                    self.handle_command(Some(Message::Select(file, true)));
+                }
+            }
+            Err(err) => {
+                log::error!("Error writing sauce: {err}");
+                self.toasts
+                    .error(fl!(crate::LANGUAGE_LOADER, "toast-sauce-write-error", error = err.to_string()))
+                    .set_duration(Some(Duration::from_secs(5)));
+            }
+        }
+    }
+
     fn open(&mut self, file: usize) -> bool {
-        if self.open_selected(file) && !self.file_view.files.is_empty() {
-            self.file_view.selected_file = Some(0);
-            self.file_view.scroll_pos = Some(0);
-            self.view_selected(file, false);
+        if self.open_selected(file) {
+            // folders are listed in the background, the first entry is selected once the listing is done
+            let command = self.file_view.select_first();
+            self.handle_command(command);
             true
         } else {
             if let Some(file) = self.file_view.files.get(file) {
-                self.opened_file = Some(file.clone());
+                let mut file = file.clone();
+                if let Err(err) = file.load_data() {
+                    log::error!("Error reading archive entry: {err}");
+                }
+                self.opened_file = Some(file);
             }
 
             false
@@ -683,16 +1462,25 @@ impl<'a> MainWindow<'a> {
     }
 }
 
-fn is_binary(file_entry: &FileEntry) -> bool {
-    if let Err(err) = file_entry.get_data(|_, data| {
-        for i in data.iter().take(500) {
-            if i == &0 || i == &255 {
-                return true;
-            }
+/// Shows the progress of the running export or conversion with a button to stop it.
+fn show_task_progress(ui: &mut egui::Ui, task: &BackgroundTask) {
+    let progress = task.get_progress();
+    ui.horizontal(|ui| {
+        ui.spinner();
+        let (done, total) = (progress.get_done(), progress.get_total());
+        ui.label(match task.kind {
+            TaskKind::Export => fl!(crate::LANGUAGE_LOADER, "label-export-status", done = done, total = total),
+            TaskKind::Convert => fl!(crate::LANGUAGE_LOADER, "label-convert-status", done = done, total = total),
+        });
+        if ui.button(fl!(crate::LANGUAGE_LOADER, "button-stop")).clicked() {
+            task.cancel();
         }
-        false
-    }) {
-        log::warn!("Error while checking if file is binary: {}", err);
-    }
-    true
+    });
+}
+
+fn convert_entry(entry: &FileEntry, output: &Path, extension: &str, options: &ConvertOptions) -> anyhow::Result<()> {
+    convert::check_output(&entry.file_info.path, output)?;
+    let buf = entry.get_data(|path, data| icy_engine::Buffer::from_bytes(path, true, data))??;
+    std::fs::write(output, convert::convert_buffer(&buf, extension, options)?)?;
+    Ok(())
 }
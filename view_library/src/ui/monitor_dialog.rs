@@ -0,0 +1,127 @@
+use eframe::egui::{self, Slider};
+use i18n_embed_fl::fl;
+
+use super::options::{MonitorOptions, MonitorTint};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Preset {
+    Clean,
+    Amber,
+    GreenPhosphor,
+    Crt,
+}
+
+const PRESETS: [Preset; 4] = [Preset::Clean, Preset::Amber, Preset::GreenPhosphor, Preset::Crt];
+
+impl Preset {
+    fn get_title(&self) -> String {
+        match self {
+            Preset::Clean => fl!(crate::LANGUAGE_LOADER, "monitor-preset-clean"),
+            Preset::Amber => fl!(crate::LANGUAGE_LOADER, "monitor-preset-amber"),
+            Preset::GreenPhosphor => fl!(crate::LANGUAGE_LOADER, "monitor-preset-green"),
+            Preset::Crt => fl!(crate::LANGUAGE_LOADER, "monitor-preset-crt"),
+        }
+    }
+
+    fn get_options(&self) -> MonitorOptions {
+        match self {
+            Preset::Clean => MonitorOptions::default(),
+            Preset::Amber => MonitorOptions {
+                use_filter: true,
+                tint: MonitorTint::Amber,
+                curvature: 0.0,
+                scanlines: 0.0,
+                ..Default::default()
+            },
+            Preset::GreenPhosphor => MonitorOptions {
+                use_filter: true,
+                tint: MonitorTint::Green,
+                blur: 40.0,
+                curvature: 0.0,
+                scanlines: 20.0,
+                ..Default::default()
+            },
+            Preset::Crt => MonitorOptions {
+                use_filter: true,
+                brightness: 40.0,
+                light: 50.0,
+                blur: 40.0,
+                curvature: 40.0,
+                scanlines: 50.0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+pub enum Message {
+    CloseDialog,
+}
+
+/// Window for the CRT emulation, changes are shown while editing.
+pub struct MonitorDialog {}
+
+impl MonitorDialog {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, options: &mut MonitorOptions) -> Option<Message> {
+        let mut message = None;
+        let mut open = true;
+        egui::Window::new(fl!(crate::LANGUAGE_LOADER, "monitor-dialog-title"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "monitor-presets"));
+                    for preset in PRESETS {
+                        let preset_options = preset.get_options();
+                        if ui.selectable_label(*options == preset_options, preset.get_title()).clicked() {
+                            *options = preset_options;
+                        }
+                    }
+                });
+                ui.separator();
+
+                ui.checkbox(&mut options.use_filter, fl!(crate::LANGUAGE_LOADER, "monitor-use-filter"));
+                ui.add_enabled_ui(options.use_filter, |ui| {
+                    egui::Grid::new("monitor_settings_grid").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "monitor-tint"));
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut options.tint, MonitorTint::Color, fl!(crate::LANGUAGE_LOADER, "monitor-tint-color"));
+                            ui.radio_value(&mut options.tint, MonitorTint::Grayscale, fl!(crate::LANGUAGE_LOADER, "monitor-tint-grayscale"));
+                            ui.radio_value(&mut options.tint, MonitorTint::Amber, fl!(crate::LANGUAGE_LOADER, "monitor-tint-amber"));
+                            ui.radio_value(&mut options.tint, MonitorTint::Green, fl!(crate::LANGUAGE_LOADER, "monitor-tint-green"));
+                        });
+                        ui.end_row();
+
+                        let sliders = [
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-brightness"), &mut options.brightness),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-contrast"), &mut options.contrast),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-saturation"), &mut options.saturation),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-gamma"), &mut options.gamma),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-light"), &mut options.light),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-blur"), &mut options.blur),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-curvature"), &mut options.curvature),
+                            (fl!(crate::LANGUAGE_LOADER, "monitor-scanlines"), &mut options.scanlines),
+                        ];
+                        for (label, value) in sliders {
+                            ui.label(label);
+                            ui.add(Slider::new(value, 0.0..=100.0).fixed_decimals(0));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "button-ok")).clicked() {
+                    message = Some(Message::CloseDialog);
+                }
+            });
+        if !open {
+            message = Some(Message::CloseDialog);
+        }
+        message
+    }
+}
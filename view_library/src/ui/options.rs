@@ -1,8 +1,16 @@
+use icy_engine_gui::MonitorSettings;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 const SCROLL_SPEED: [f32; 3] = [80.0, 160.0, 320.0];
 
+/// Number of recent locations that are kept.
+const MAX_RECENT: usize = 20;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ScrollSpeed {
     Slow,
@@ -28,10 +36,201 @@ impl ScrollSpeed {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum ViewMode {
+    #[default]
+    List,
+    Grid,
+    Table,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Title,
+    Author,
+    Group,
+    Date,
+    Dimensions,
+}
+
+impl SortColumn {
+    pub fn needs_sauce(&self) -> bool {
+        matches!(
+            self,
+            SortColumn::Title | SortColumn::Author | SortColumn::Group | SortColumn::Date | SortColumn::Dimensions
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum ScaleMode {
+    /// Fills the width of the view, at most at twice the original size.
+    #[default]
+    FitWidth,
+    /// Shows the whole buffer.
+    FitPage,
+    /// Uses the zoom factor of the options.
+    Zoom,
+}
+
+pub const MIN_ZOOM: f32 = 0.25;
+pub const MAX_ZOOM: f32 = 8.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SlideshowOptions {
+    /// Seconds each piece is shown.
+    pub delay: u64,
+    /// Scrolling pieces are shown until they reach the end, even if the delay is over.
+    pub wait_for_end: bool,
+    pub shuffle: bool,
+    /// Start over after the last piece.
+    pub repeat: bool,
+    /// Include the files of all sub folders.
+    pub recursive: bool,
+}
+
+impl Default for SlideshowOptions {
+    fn default() -> Self {
+        Self {
+            delay: 10,
+            wait_for_end: true,
+            shuffle: false,
+            repeat: true,
+            recursive: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum MonitorTint {
+    #[default]
+    Color,
+    Grayscale,
+    Amber,
+    Green,
+}
+
+/// CRT emulation of the buffer view, the values go from 0 to 100.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct MonitorOptions {
+    /// Without the filter the buffer is shown as it is.
+    pub use_filter: bool,
+    pub tint: MonitorTint,
+    pub brightness: f32,
+    pub contrast: f32,
+    pub saturation: f32,
+    pub gamma: f32,
+    pub light: f32,
+    pub blur: f32,
+    pub curvature: f32,
+    pub scanlines: f32,
+}
+
+impl Default for MonitorOptions {
+    fn default() -> Self {
+        Self {
+            use_filter: false,
+            tint: MonitorTint::Color,
+            brightness: 30.0,
+            contrast: 50.0,
+            saturation: 50.0,
+            gamma: 50.0,
+            light: 40.0,
+            blur: 30.0,
+            curvature: 10.0,
+            scanlines: 10.0,
+        }
+    }
+}
+
+impl MonitorOptions {
+    pub fn get_monitor_settings(&self) -> MonitorSettings {
+        MonitorSettings {
+            use_filter: self.use_filter,
+            monitor_type: self.tint as usize,
+            brightness: self.brightness,
+            contrast: self.contrast,
+            saturation: self.saturation,
+            gamma: self.gamma,
+            light: self.light,
+            blur: self.blur,
+            curvature: self.curvature,
+            scanlines: self.scanlines,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecentLocation {
+    pub path: PathBuf,
+    /// File name of the entry that was selected last.
+    pub selected_file: Option<String>,
+}
+
+/// Shortcuts of an action in the `[keys]` table, either `"Ctrl+J"` or `["F11", "Alt+Enter"]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl KeyBinding {
+    pub fn get_shortcuts(&self) -> Vec<&str> {
+        match self {
+            KeyBinding::Single(shortcut) => vec![shortcut.as_str()],
+            KeyBinding::Multiple(shortcuts) => shortcuts.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Options {
     pub auto_scroll_enabled: bool,
     pub scroll_speed: ScrollSpeed,
+    #[serde(default)]
+    pub view_mode: ViewMode,
+    #[serde(default)]
+    pub sort_column: SortColumn,
+    #[serde(default)]
+    pub sort_descending: bool,
+    #[serde(default)]
+    pub slideshow: SlideshowOptions,
+    /// Speed of the baud rate playback, 0 is unlimited.
+    #[serde(default = "default_baud_rate")]
+    pub baud_rate: u32,
+    #[serde(default)]
+    pub scale_mode: ScaleMode,
+    #[serde(default = "default_zoom")]
+    pub zoom: f32,
+    /// Rounds the scale down to whole numbers, so every pixel of the font has the same size.
+    #[serde(default)]
+    pub integer_scaling: bool,
+    #[serde(default)]
+    pub monitor: MonitorOptions,
+    #[serde(default)]
+    pub bookmarks: Vec<PathBuf>,
+    /// Most recent first.
+    #[serde(default)]
+    pub recent: Vec<RecentLocation>,
+    /// Shortcuts that replace the default ones, keyed by action name. An empty list unbinds the action.
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyBinding>,
+}
+
+fn default_baud_rate() -> u32 {
+    14400
+}
+
+fn default_zoom() -> f32 {
+    1.0
 }
 
 impl Default for Options {
@@ -39,11 +238,48 @@ impl Default for Options {
         Self {
             auto_scroll_enabled: true,
             scroll_speed: ScrollSpeed::Medium,
+            view_mode: ViewMode::List,
+            sort_column: SortColumn::Name,
+            sort_descending: false,
+            slideshow: SlideshowOptions::default(),
+            baud_rate: default_baud_rate(),
+            scale_mode: ScaleMode::FitWidth,
+            zoom: default_zoom(),
+            integer_scaling: false,
+            monitor: MonitorOptions::default(),
+            bookmarks: Vec::new(),
+            recent: Vec::new(),
+            keys: BTreeMap::new(),
         }
     }
 }
 
 impl Options {
+    pub fn is_bookmarked(&self, path: &Path) -> bool {
+        self.bookmarks.iter().any(|bookmark| bookmark == path)
+    }
+
+    pub fn toggle_bookmark(&mut self, path: PathBuf) {
+        if let Some(i) = self.bookmarks.iter().position(|bookmark| *bookmark == path) {
+            self.bookmarks.remove(i);
+        } else {
+            self.bookmarks.push(path);
+        }
+    }
+
+    /// Moves `path` to the top of the recent locations.
+    pub fn add_recent(&mut self, path: PathBuf, selected_file: Option<String>) {
+        self.recent.retain(|location| location.path != path);
+        self.recent.insert(0, RecentLocation { path, selected_file });
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    /// Switches to a fixed zoom factor.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.scale_mode = ScaleMode::Zoom;
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
     pub fn load_options() -> Self {
         if let Some(proj_dirs) = directories::ProjectDirs::from("com", "GitHub", "icy_view") {
             if !proj_dirs.config_dir().exists() && fs::create_dir_all(proj_dirs.config_dir()).is_err() {
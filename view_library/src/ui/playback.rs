@@ -0,0 +1,182 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, Ui};
+use i18n_embed_fl::fl;
+use icy_engine::{avatar, pcboard, Buffer, BufferParser, Caret};
+use icy_engine_gui::BufferView;
+
+use super::{loader, sauce_writer};
+
+pub const BAUD_RATES: [u32; 11] = [300, 1200, 2400, 4800, 9600, 14400, 19200, 28800, 38400, 57600, 115200];
+
+/// Files that are played back through their parser.
+const PLAYBACK_EXTENSIONS: [&str; 9] = ["ans", "ice", "asc", "txt", "nfo", "diz", "bbs", "pcb", "avt"];
+
+pub fn is_supported(path: &Path) -> bool {
+    PLAYBACK_EXTENSIONS.contains(&loader::get_extension(path).as_str())
+}
+
+fn get_parser(path: &Path) -> Box<dyn BufferParser> {
+    match loader::get_extension(path).as_str() {
+        "pcb" => Box::<pcboard::Parser>::default(),
+        "avt" => Box::<avatar::Parser>::default(),
+        _ => Box::<icy_engine::ansi::Parser>::default(),
+    }
+}
+
+/// Feeds a file to its parser at modem speed, so cursor movement and redraws are shown as they were drawn.
+pub struct Playback {
+    path: PathBuf,
+    /// The SAUCE record, used to set up an empty buffer with the settings of the file.
+    sauce_part: Vec<u8>,
+    content: Vec<u8>,
+    parser: Box<dyn BufferParser>,
+    position: usize,
+    /// Fraction of a byte left over from the last update.
+    pending: f32,
+    pub paused: bool,
+}
+
+impl Playback {
+    pub fn new(path: &Path, data: &[u8]) -> Self {
+        let content_len = sauce_writer::strip_sauce(data).len();
+        Self {
+            path: path.to_path_buf(),
+            sauce_part: data[content_len..].to_vec(),
+            content: data[..content_len].to_vec(),
+            parser: get_parser(path),
+            position: 0,
+            pending: 0.0,
+            paused: false,
+        }
+    }
+
+    /// Starts over with an empty buffer.
+    pub fn restart(&mut self, buffer_view: &mut BufferView) -> anyhow::Result<()> {
+        let buffer = Buffer::from_bytes(&self.path, true, &self.sauce_part)?;
+        buffer_view.set_buffer(buffer);
+        *buffer_view.get_caret_mut() = Caret::default();
+        buffer_view.get_caret_mut().set_is_visible(false);
+        self.parser = get_parser(&self.path);
+        self.position = 0;
+        self.pending = 0.0;
+        Ok(())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.content.len()
+    }
+
+    /// Feeds the bytes received in `dt` seconds, a baud rate of 0 shows the whole file at once.
+    pub fn update(&mut self, buffer_view: &mut BufferView, dt: f32, baud_rate: u32) {
+        if self.paused || self.is_finished() {
+            return;
+        }
+        if baud_rate == 0 {
+            self.feed(buffer_view, self.content.len());
+            return;
+        }
+        // 8N1: ten bits per byte
+        self.pending += baud_rate as f32 / 10.0 * dt;
+        let count = self.pending as usize;
+        self.pending -= count as f32;
+        self.feed(buffer_view, count);
+    }
+
+    /// Feeds the rest of the current line.
+    pub fn step(&mut self, buffer_view: &mut BufferView) {
+        let rest = &self.content[self.position..];
+        let count = rest.iter().position(|b| *b == b'\n').map_or(rest.len(), |i| i + 1);
+        self.feed(buffer_view, count);
+    }
+
+    pub fn seek(&mut self, buffer_view: &mut BufferView, position: usize) {
+        if position < self.position {
+            if let Err(err) = self.restart(buffer_view) {
+                log::error!("Error restarting playback: {err}");
+                return;
+            }
+        }
+        self.feed(buffer_view, position - self.position);
+    }
+
+    fn feed(&mut self, buffer_view: &mut BufferView, count: usize) {
+        let end = (self.position + count).min(self.content.len());
+        for &byte in &self.content[self.position..end] {
+            if let Err(err) = buffer_view.print_char(&mut *self.parser, char::from(byte)) {
+                log::warn!("Error during playback: {err}");
+            }
+        }
+        self.position = end;
+    }
+
+    /// Shows the playback controls, returns true if playback should be closed.
+    pub fn show_controls(&mut self, ui: &mut Ui, buffer_view: &mut BufferView, baud_rate: &mut u32) -> bool {
+        let mut close = false;
+        ui.horizontal(|ui| {
+            if ui.button("⏮").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-playback-restart")).clicked() {
+                if let Err(err) = self.restart(buffer_view) {
+                    log::error!("Error restarting playback: {err}");
+                }
+            }
+            let (icon, tooltip) = if self.paused {
+                ("▶", fl!(crate::LANGUAGE_LOADER, "tooltip-playback-play"))
+            } else {
+                ("⏸", fl!(crate::LANGUAGE_LOADER, "tooltip-playback-pause"))
+            };
+            if ui.button(icon).on_hover_text(tooltip).clicked() {
+                self.paused = !self.paused;
+            }
+            if ui.button("⏭").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-playback-step")).clicked() {
+                self.paused = true;
+                self.step(buffer_view);
+            }
+
+            egui::ComboBox::from_id_source("baud_rate")
+                .selected_text(get_baud_rate_text(*baud_rate))
+                .show_ui(ui, |ui| {
+                    for rate in BAUD_RATES.into_iter().chain(std::iter::once(0)) {
+                        ui.selectable_value(baud_rate, rate, get_baud_rate_text(rate));
+                    }
+                });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("🗙").on_hover_text(fl!(crate::LANGUAGE_LOADER, "tooltip-playback-close")).clicked() {
+                    close = true;
+                }
+                ui.label(format!("{}/{}", self.position, self.content.len()));
+                let mut position = self.position;
+                ui.spacing_mut().slider_width = ui.available_width() - 8.0;
+                if ui.add(egui::Slider::new(&mut position, 0..=self.content.len()).show_value(false)).changed() {
+                    self.seek(buffer_view, position);
+                }
+            });
+        });
+        close
+    }
+}
+
+/// Plays a file back without a view, `on_frame` gets the screen after every `bytes_per_frame` bytes.
+pub fn record(path: &Path, data: &[u8], bytes_per_frame: usize, mut on_frame: impl FnMut(&Buffer, &Caret) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let content_len = sauce_writer::strip_sauce(data).len();
+    let mut buffer = Buffer::from_bytes(path, true, &data[content_len..])?;
+    let mut caret = Caret::default();
+    let mut parser = get_parser(path);
+    for chunk in data[..content_len].chunks(bytes_per_frame.max(1)) {
+        for &byte in chunk {
+            if let Err(err) = parser.print_char(&mut buffer, 0, &mut caret, char::from(byte)) {
+                log::warn!("Error during playback: {err}");
+            }
+        }
+        on_frame(&buffer, &caret)?;
+    }
+    Ok(())
+}
+
+fn get_baud_rate_text(baud_rate: u32) -> String {
+    if baud_rate == 0 {
+        fl!(crate::LANGUAGE_LOADER, "playback-unlimited")
+    } else {
+        fl!(crate::LANGUAGE_LOADER, "playback-baud-rate", rate = baud_rate)
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use eframe::egui::{self, Modifiers, TextEdit};
+use i18n_embed_fl::fl;
+
+use super::options::Options;
+
+pub enum Message {
+    Close,
+    Jump(PathBuf, Option<String>),
+}
+
+struct JumpTarget {
+    path: PathBuf,
+    selected_file: Option<String>,
+    is_bookmark: bool,
+}
+
+/// Popup that jumps to a bookmark or recent location, typing filters the list by path.
+pub struct QuickJump {
+    filter: String,
+    selected: usize,
+    request_focus: bool,
+}
+
+impl QuickJump {
+    pub fn new() -> Self {
+        Self {
+            filter: String::new(),
+            selected: 0,
+            request_focus: true,
+        }
+    }
+
+    fn get_targets(&self, options: &Options) -> Vec<JumpTarget> {
+        let bookmarks = options.bookmarks.iter().map(|path| JumpTarget {
+            path: path.clone(),
+            selected_file: None,
+            is_bookmark: true,
+        });
+        let recent = options
+            .recent
+            .iter()
+            .filter(|location| !options.is_bookmarked(&location.path))
+            .map(|location| JumpTarget {
+                path: location.path.clone(),
+                selected_file: location.selected_file.clone(),
+                is_bookmark: false,
+            });
+        let filter = self.filter.to_lowercase();
+        bookmarks
+            .chain(recent)
+            .filter(|target| target.path.to_string_lossy().to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, options: &Options) -> Option<Message> {
+        let mut message = None;
+        let targets = self.get_targets(options);
+        self.selected = self.selected.min(targets.len().saturating_sub(1));
+
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, egui::Key::ArrowDown)) && self.selected + 1 < targets.len() {
+            self.selected += 1;
+        }
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, egui::Key::ArrowUp)) {
+            self.selected = self.selected.saturating_sub(1);
+        }
+
+        egui::Window::new(fl!(crate::LANGUAGE_LOADER, "quick-jump-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.filter)
+                        .hint_text(fl!(crate::LANGUAGE_LOADER, "quick-jump-hint-text"))
+                        .desired_width(400.0),
+                );
+                if self.request_focus {
+                    response.request_focus();
+                    self.request_focus = false;
+                }
+                if response.changed() {
+                    self.selected = 0;
+                }
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    match targets.get(self.selected) {
+                        Some(target) => message = Some(Message::Jump(target.path.clone(), target.selected_file.clone())),
+                        None => message = Some(Message::Close),
+                    }
+                }
+
+                ui.separator();
+                if targets.is_empty() {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "quick-jump-empty"));
+                }
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (i, target) in targets.iter().enumerate() {
+                        let icon = if target.is_bookmark { "★" } else { "🕓" };
+                        let response = ui
+                            .selectable_label(i == self.selected, format!("{icon} {}", target.path.display()))
+                            .on_hover_text(target.selected_file.clone().unwrap_or_default());
+                        if i == self.selected {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            message = Some(Message::Jump(target.path.clone(), target.selected_file.clone()));
+                        }
+                    }
+                });
+            });
+        message
+    }
+}
@@ -1,24 +1,66 @@
+use std::path::PathBuf;
+
 use eframe::egui::{self, Layout};
 use egui_modal::Modal;
 use i18n_embed_fl::fl;
 use icy_sauce::SauceInformation;
 
+use super::sauce_writer::{SauceEdit, SauceFlags, AUTHOR_LEN, FONT_LEN, GROUP_LEN, TITLE_LEN};
+
 pub struct SauceDialog {
-    sauce: SauceInformation,
+    /// Path of the edited entry, its index changes when the list is sorted or updated while the dialog is open.
+    file: PathBuf,
+    edit: SauceEdit,
+    /// Reason why the SAUCE can't be written back, `None` if it can.
+    read_only_reason: Option<String>,
 }
 
 pub enum Message {
     CloseDialog,
+    Save(PathBuf, SauceEdit),
+    Remove(PathBuf),
 }
 
 impl SauceDialog {
-    pub fn new(sauce: SauceInformation) -> Self {
-        Self { sauce }
+    pub fn new(file: PathBuf, sauce: SauceInformation, writable: Result<(), String>) -> Self {
+        let mut comments = String::new();
+        for s in sauce.comments() {
+            comments.push_str(&s.to_string());
+            comments.push('\n');
+        }
+
+        let mut font = None;
+        let mut flags = None;
+        if let Ok(caps) = sauce.get_character_capabilities() {
+            font = Some(caps.font_opt.as_ref().map(|font| font.to_string()).unwrap_or_default());
+            flags = Some(SauceFlags {
+                use_ice: caps.use_ice,
+                use_letter_spacing: caps.use_letter_spacing,
+                use_aspect_ratio: caps.use_aspect_ratio,
+            });
+        }
+
+        let edit = SauceEdit {
+            title: sauce.title().to_string().trim_end().to_string(),
+            author: sauce.author().to_string().trim_end().to_string(),
+            group: sauce.group().to_string().trim_end().to_string(),
+            date: sauce.get_date().map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            comments,
+            font,
+            flags,
+        };
+
+        Self {
+            file,
+            edit,
+            read_only_reason: writable.err(),
+        }
     }
 
     pub fn show(&mut self, ctx: &egui::Context) -> Option<Message> {
         let mut message = None;
-        let modal = Modal::new(ctx, "protocol_modal");
+        let modal = Modal::new(ctx, "sauce_modal");
+        let validation = self.edit.validate();
         modal.show(|ui| {
             modal.title(ui, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-title"));
 
@@ -27,84 +69,90 @@ impl SauceDialog {
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-title-label"));
                     });
-                    ui.add(egui::TextEdit::singleline(&mut self.sauce.title().to_string().as_str()).char_limit(35));
+                    ui.add(egui::TextEdit::singleline(&mut self.edit.title).char_limit(TITLE_LEN));
                     ui.end_row();
 
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-author-label"));
                     });
 
-                    ui.add(egui::TextEdit::singleline(&mut self.sauce.author().to_string().as_str()).char_limit(20));
+                    ui.add(egui::TextEdit::singleline(&mut self.edit.author).char_limit(AUTHOR_LEN));
                     ui.end_row();
 
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-group-label"));
                     });
-                    ui.add(egui::TextEdit::singleline(&mut self.sauce.group().to_string().as_str()).char_limit(20));
+                    ui.add(egui::TextEdit::singleline(&mut self.edit.group).char_limit(GROUP_LEN));
                     ui.end_row();
 
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-date-label"));
                     });
-                    let t = self.sauce.get_date().unwrap().format("%Y-%m-%d").to_string();
-                    ui.add(egui::TextEdit::singleline(&mut t.as_str()).char_limit(20));
+                    ui.add(egui::TextEdit::singleline(&mut self.edit.date).char_limit(10).hint_text("YYYY-MM-DD"));
                     ui.end_row();
-                    if let Ok(caps) = self.sauce.get_character_capabilities() {
-                        if let Some(font) = &caps.font_opt {
-                            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                                ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-font-name"));
-                            });
-                            ui.add(egui::TextEdit::singleline(&mut font.to_string()).char_limit(20));
-                            ui.end_row();
-                        }
 
+                    if let Some(font) = &mut self.edit.font {
                         ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flags-label"));
+                            ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-font-name"));
                         });
-                        let mut flags: String = String::new();
-                        if caps.use_ice {
-                            flags.push_str("ice colors");
-                        }
-
-                        if caps.use_letter_spacing {
-                            if !flags.is_empty() {
-                                flags.push_str(", ");
-                            }
-                            flags.push_str("letter spacing");
-                        }
-
-                        if caps.use_aspect_ratio {
-                            if !flags.is_empty() {
-                                flags.push_str(", ");
-                            }
-                            flags.push_str("aspect ratio");
-                        }
-                        ui.add(egui::TextEdit::singleline(&mut flags.to_string().as_str()).char_limit(20));
+                        ui.add(egui::TextEdit::singleline(font).char_limit(FONT_LEN));
+                        ui.end_row();
                     }
 
-                    ui.end_row();
+                    if let Some(flags) = &mut self.edit.flags {
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flags-label"));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut flags.use_ice, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-ice"));
+                            ui.checkbox(&mut flags.use_letter_spacing, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-letter-spacing"));
+                            ui.checkbox(&mut flags.use_aspect_ratio, fl!(crate::LANGUAGE_LOADER, "sauce-dialog-flag-aspect-ratio"));
+                        });
+                        ui.end_row();
+                    }
                 });
 
-                let mut tmp_str = String::new();
-                for s in self.sauce.comments() {
-                    tmp_str.push_str(&s.to_string());
-                    tmp_str.push('\n');
-                }
+                ui.add_space(16.0);
+                ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-comments-label"));
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical().max_height(180.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.edit.comments)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_rows(6)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
 
-                if !tmp_str.is_empty() {
-                    ui.add_space(16.0);
-                    ui.label(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-comments-label"));
-                    ui.add_space(4.0);
-                    egui::ScrollArea::vertical().max_height(180.0).show(ui, |ui| {
-                        ui.add(egui::TextEdit::multiline(&mut tmp_str.as_str()).desired_rows(6).desired_width(f32::INFINITY));
-                    });
+                if let Some(reason) = &self.read_only_reason {
+                    ui.add_space(8.0);
+                    ui.colored_label(
+                        ui.style().visuals.warn_fg_color,
+                        fl!(crate::LANGUAGE_LOADER, "sauce-dialog-read-only", reason = reason.clone()),
+                    );
+                } else if let Err(err) = &validation {
+                    ui.add_space(8.0);
+                    ui.colored_label(ui.style().visuals.error_fg_color, err);
                 }
             });
 
             modal.buttons(ui, |ui| {
-                if modal.button(ui, fl!(crate::LANGUAGE_LOADER, "button-ok")).clicked() {
+                if modal.button(ui, fl!(crate::LANGUAGE_LOADER, "button-cancel")).clicked() {
                     message = Some(Message::CloseDialog);
                 }
+                let writable = self.read_only_reason.is_none();
+                if ui
+                    .add_enabled(writable && validation.is_ok(), egui::Button::new(fl!(crate::LANGUAGE_LOADER, "button-save")))
+                    .clicked()
+                {
+                    message = Some(Message::Save(self.file.clone(), self.edit.clone()));
+                }
+                if ui
+                    .add_enabled(writable, egui::Button::new(fl!(crate::LANGUAGE_LOADER, "sauce-dialog-remove-button")))
+                    .clicked()
+                {
+                    message = Some(Message::Remove(self.file.clone()));
+                }
             });
         });
         modal.open();
@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread,
+};
+
+use icy_sauce::SauceInformation;
+
+use super::file_view::FileEntry;
+
+struct SauceRequest {
+    generation: usize,
+    entry: FileEntry,
+}
+
+struct SauceResult {
+    generation: usize,
+    path: PathBuf,
+    sauce: Option<SauceInformation>,
+}
+
+/// Reads the SAUCE records of all files of a folder on a worker thread.
+pub struct SauceLoader {
+    generation: Arc<AtomicUsize>,
+    pending: usize,
+    request_tx: Sender<SauceRequest>,
+    result_rx: Receiver<SauceResult>,
+}
+
+impl Default for SauceLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SauceLoader {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = channel::<SauceRequest>();
+        let (result_tx, result_rx) = channel();
+        let generation = Arc::new(AtomicUsize::new(0));
+
+        let thread_generation = generation.clone();
+        thread::spawn(move || {
+            while let Ok(request) = request_rx.recv() {
+                // skip requests of folders that are no longer shown
+                if request.generation != thread_generation.load(Ordering::Relaxed) {
+                    continue;
+                }
+                let sauce = match request.entry.get_data(|_, data| SauceInformation::read(data)) {
+                    Ok(Ok(sauce)) => sauce,
+                    _ => None,
+                };
+                let result = SauceResult {
+                    generation: request.generation,
+                    path: request.entry.file_info.path.clone(),
+                    sauce,
+                };
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            generation,
+            pending: 0,
+            request_tx,
+            result_rx,
+        }
+    }
+
+    /// Drops pending requests and queues all files whose SAUCE hasn't been read yet.
+    pub fn load(&mut self, files: &[FileEntry]) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        self.pending = 0;
+        for entry in files.iter().filter(|entry| entry.is_file() && !entry.read_sauce) {
+            let request = SauceRequest {
+                generation,
+                entry: entry.clone(),
+            };
+            if self.request_tx.send(request).is_err() {
+                break;
+            }
+            self.pending += 1;
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Stores the finished records in the entries, returns true if any entry got updated.
+    pub fn update(&mut self, files: &mut [FileEntry]) -> bool {
+        let generation = self.generation.load(Ordering::Relaxed);
+        let mut results = Vec::new();
+        while let Ok(result) = self.result_rx.try_recv() {
+            if result.generation == generation {
+                results.push(result);
+            }
+        }
+        if results.is_empty() {
+            return false;
+        }
+        self.pending = self.pending.saturating_sub(results.len());
+
+        let index: HashMap<&PathBuf, usize> = files.iter().enumerate().map(|(i, entry)| (&entry.file_info.path, i)).collect();
+        let updates: Vec<(usize, Option<SauceInformation>)> = results
+            .into_iter()
+            .filter_map(|result| index.get(&result.path).map(|i| (*i, result.sauce)))
+            .collect();
+        for (i, sauce) in updates {
+            let entry = &mut files[i];
+            if !entry.read_sauce {
+                entry.read_sauce = true;
+                entry.sauce = sauce;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,382 @@
+use std::fs;
+
+use i18n_embed_fl::fl;
+
+use super::{file_operations, file_view::FileEntry};
+
+// Field lengths from the SAUCE 00.5 spec.
+pub const TITLE_LEN: usize = 35;
+pub const AUTHOR_LEN: usize = 20;
+pub const GROUP_LEN: usize = 20;
+pub const FONT_LEN: usize = 22;
+pub const COMMENT_LEN: usize = 64;
+pub const MAX_COMMENTS: usize = 255;
+
+const RECORD_LEN: usize = 128;
+const EOF_CHAR: u8 = 0x1A;
+
+const TITLE_OFFSET: usize = 7;
+const AUTHOR_OFFSET: usize = 42;
+const GROUP_OFFSET: usize = 62;
+const DATE_OFFSET: usize = 82;
+const FILE_SIZE_OFFSET: usize = 90;
+const COMMENTS_OFFSET: usize = 104;
+const FLAGS_OFFSET: usize = 105;
+const FONT_OFFSET: usize = 106;
+
+const ICE_FLAG: u8 = 0b0000_0001;
+const LETTER_SPACING_MASK: u8 = 0b0000_0110;
+const LETTER_SPACING_9PX: u8 = 0b0000_0100;
+const LETTER_SPACING_8PX: u8 = 0b0000_0010;
+const ASPECT_RATIO_MASK: u8 = 0b0001_1000;
+const ASPECT_RATIO_LEGACY: u8 = 0b0000_1000;
+const ASPECT_RATIO_SQUARE: u8 = 0b0001_0000;
+
+/// Flags stored in the TFlags byte of character based files.
+#[derive(Clone, Copy, Default)]
+pub struct SauceFlags {
+    pub use_ice: bool,
+    pub use_letter_spacing: bool,
+    pub use_aspect_ratio: bool,
+}
+
+/// The editable fields of a SAUCE record, all other fields of the original record are kept.
+#[derive(Clone, Default)]
+pub struct SauceEdit {
+    pub title: String,
+    pub author: String,
+    pub group: String,
+    /// Date in `YYYY-MM-DD` or `YYYYMMDD` format, empty for no date.
+    pub date: String,
+    pub comments: String,
+    pub font: Option<String>,
+    pub flags: Option<SauceFlags>,
+}
+
+impl SauceEdit {
+    pub fn validate(&self) -> Result<(), String> {
+        check_field(&fl!(crate::LANGUAGE_LOADER, "sauce-field-title"), &self.title, TITLE_LEN)?;
+        check_field(&fl!(crate::LANGUAGE_LOADER, "sauce-field-author"), &self.author, AUTHOR_LEN)?;
+        check_field(&fl!(crate::LANGUAGE_LOADER, "sauce-field-group"), &self.group, GROUP_LEN)?;
+        if let Some(font) = &self.font {
+            check_field(&fl!(crate::LANGUAGE_LOADER, "sauce-field-font"), font, FONT_LEN)?;
+            if !font.is_ascii() {
+                return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-font-ascii"));
+            }
+        }
+        parse_date(&self.date)?;
+
+        let comments = self.get_comments();
+        if comments.len() > MAX_COMMENTS {
+            return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-too-many-comments", max = MAX_COMMENTS));
+        }
+        for (i, line) in comments.iter().enumerate() {
+            check_field(&fl!(crate::LANGUAGE_LOADER, "sauce-field-comment-line", line = i + 1), line, COMMENT_LEN)?;
+        }
+        Ok(())
+    }
+
+    fn get_comments(&self) -> Vec<&str> {
+        let comments = self.comments.trim_end_matches('\n');
+        if comments.is_empty() {
+            return Vec::new();
+        }
+        comments.lines().collect()
+    }
+}
+
+fn check_field(name: &str, text: &str, max_len: usize) -> Result<(), String> {
+    let len = text.chars().count();
+    if len > max_len {
+        return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-too-long", field = name, len = len, max = max_len));
+    }
+    if let Some(ch) = text.chars().find(|ch| encode_cp437(*ch).is_none()) {
+        return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-not-cp437", field = name, character = ch.to_string()));
+    }
+    Ok(())
+}
+
+fn parse_date(date: &str) -> Result<[u8; 8], String> {
+    let mut result = [b' '; 8];
+    let digits: String = date.chars().filter(|ch| *ch != '-').collect();
+    if digits.is_empty() {
+        return Ok(result);
+    }
+    if digits.len() != 8 || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-invalid-date", date = date));
+    }
+    let month: u32 = digits[4..6].parse().unwrap_or_default();
+    let day: u32 = digits[6..8].parse().unwrap_or_default();
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-invalid-date", date = date));
+    }
+    result.copy_from_slice(digits.as_bytes());
+    Ok(result)
+}
+
+/// Returns the content of a file without the EOF char, comment block and SAUCE record.
+pub fn strip_sauce(data: &[u8]) -> &[u8] {
+    split_sauce(data).0
+}
+
+fn split_sauce(data: &[u8]) -> (&[u8], Option<&[u8]>) {
+    if data.len() < RECORD_LEN || &data[data.len() - RECORD_LEN..data.len() - RECORD_LEN + 7] != b"SAUCE00" {
+        return (data, None);
+    }
+    let record = &data[data.len() - RECORD_LEN..];
+    let mut end = data.len() - RECORD_LEN;
+
+    let comments = record[COMMENTS_OFFSET] as usize;
+    if comments > 0 {
+        let block_len = 5 + comments * COMMENT_LEN;
+        if end >= block_len && &data[end - block_len..end - block_len + 5] == b"COMNT" {
+            end -= block_len;
+        }
+    }
+    if end > 0 && data[end - 1] == EOF_CHAR {
+        end -= 1;
+    }
+    (&data[..end], Some(record))
+}
+
+/// Rewrites the SAUCE record of `data`, `None` removes the record and the comments.
+pub fn update_sauce(data: &[u8], edit: Option<&SauceEdit>) -> anyhow::Result<Vec<u8>> {
+    let (content, record) = split_sauce(data);
+    let Some(edit) = edit else {
+        return Ok(content.to_vec());
+    };
+    let Some(record) = record else {
+        return Err(anyhow::anyhow!(fl!(crate::LANGUAGE_LOADER, "sauce-error-no-record")));
+    };
+    edit.validate().map_err(|err| anyhow::anyhow!(err))?;
+
+    let mut record = record.to_vec();
+    write_field(&mut record[TITLE_OFFSET..TITLE_OFFSET + TITLE_LEN], &edit.title);
+    write_field(&mut record[AUTHOR_OFFSET..AUTHOR_OFFSET + AUTHOR_LEN], &edit.author);
+    write_field(&mut record[GROUP_OFFSET..GROUP_OFFSET + GROUP_LEN], &edit.group);
+    record[DATE_OFFSET..DATE_OFFSET + 8].copy_from_slice(&parse_date(&edit.date).map_err(|err| anyhow::anyhow!(err))?);
+    record[FILE_SIZE_OFFSET..FILE_SIZE_OFFSET + 4].copy_from_slice(&(content.len() as u32).to_le_bytes());
+
+    if let Some(font) = &edit.font {
+        // TInfoS is a zero terminated string
+        let field = &mut record[FONT_OFFSET..FONT_OFFSET + FONT_LEN];
+        field.fill(0);
+        field[..font.len()].copy_from_slice(font.as_bytes());
+    }
+
+    if let Some(flags) = &edit.flags {
+        let mut t_flags = record[FLAGS_OFFSET];
+        t_flags = if flags.use_ice { t_flags | ICE_FLAG } else { t_flags & !ICE_FLAG };
+        if flags.use_letter_spacing {
+            t_flags = (t_flags & !LETTER_SPACING_MASK) | LETTER_SPACING_9PX;
+        } else if t_flags & LETTER_SPACING_MASK == LETTER_SPACING_9PX {
+            t_flags = (t_flags & !LETTER_SPACING_MASK) | LETTER_SPACING_8PX;
+        }
+        if flags.use_aspect_ratio {
+            t_flags = (t_flags & !ASPECT_RATIO_MASK) | ASPECT_RATIO_LEGACY;
+        } else if t_flags & ASPECT_RATIO_MASK == ASPECT_RATIO_LEGACY {
+            t_flags = (t_flags & !ASPECT_RATIO_MASK) | ASPECT_RATIO_SQUARE;
+        }
+        record[FLAGS_OFFSET] = t_flags;
+    }
+
+    let comments = edit.get_comments();
+    record[COMMENTS_OFFSET] = comments.len() as u8;
+
+    let mut result = Vec::with_capacity(content.len() + 1 + 5 + comments.len() * COMMENT_LEN + RECORD_LEN);
+    result.extend_from_slice(content);
+    result.push(EOF_CHAR);
+    if !comments.is_empty() {
+        result.extend_from_slice(b"COMNT");
+        for line in comments {
+            let mut field = [b' '; COMMENT_LEN];
+            write_field(&mut field, line);
+            result.extend_from_slice(&field);
+        }
+    }
+    result.extend_from_slice(&record);
+    Ok(result)
+}
+
+/// Writes a space padded CP437 string.
+fn write_field(field: &mut [u8], text: &str) {
+    field.fill(b' ');
+    for (dst, ch) in field.iter_mut().zip(text.chars()) {
+        *dst = encode_cp437(ch).unwrap_or(b'?');
+    }
+}
+
+/// Checks if the SAUCE of an entry can be written, returns the reason if not.
+pub fn check_writable(entry: &FileEntry) -> Result<(), String> {
+    if let Some(archive_entry) = &entry.archive_entry {
+        return archive_entry.check_writable();
+    }
+    if entry.file_data.is_some() {
+        return Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-in-memory"));
+    }
+    match fs::metadata(&entry.file_info.path) {
+        Ok(metadata) if metadata.permissions().readonly() => Err(fl!(crate::LANGUAGE_LOADER, "sauce-error-read-only")),
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Writes the updated SAUCE of an entry back to the file or archive.
+pub fn write_sauce(entry: &FileEntry, edit: Option<&SauceEdit>) -> anyhow::Result<()> {
+    check_writable(entry).map_err(|err| anyhow::anyhow!(err))?;
+    let data = entry.get_data(|_, data| data.to_vec())?;
+    let data = update_sauce(&data, edit)?;
+    if let Some(archive_entry) = &entry.archive_entry {
+        archive_entry.write_data(&data)
+    } else {
+        file_operations::replace_file(&entry.file_info.path, |tmp_path| Ok(fs::write(tmp_path, &data)?))
+    }
+}
+
+const CP437_UPPER: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧',
+    'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜',
+    '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄',
+    '▌', '▐', '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√',
+    'ⁿ', '²', '■', '\u{A0}',
+];
+
+fn encode_cp437(ch: char) -> Option<u8> {
+    if (' '..='~').contains(&ch) {
+        return Some(ch as u8);
+    }
+    CP437_UPPER.iter().position(|c| *c == ch).map(|i| 0x80 + i as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_record(comments: u8) -> Vec<u8> {
+        let mut record = vec![b' '; RECORD_LEN];
+        record[..7].copy_from_slice(b"SAUCE00");
+        record[FILE_SIZE_OFFSET..FILE_SIZE_OFFSET + 4].fill(0);
+        record[COMMENTS_OFFSET] = comments;
+        record[FLAGS_OFFSET] = 0;
+        record[FONT_OFFSET..].fill(0);
+        record
+    }
+
+    fn create_edit(comments: &str) -> SauceEdit {
+        SauceEdit {
+            title: "Title".to_string(),
+            author: "Author".to_string(),
+            group: "Group".to_string(),
+            date: "1995-07-14".to_string(),
+            comments: comments.to_string(),
+            font: Some("IBM VGA".to_string()),
+            flags: Some(SauceFlags {
+                use_ice: true,
+                use_letter_spacing: false,
+                use_aspect_ratio: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_split_without_sauce() {
+        let data = b"no record".to_vec();
+        assert_eq!(split_sauce(&data), (data.as_slice(), None));
+    }
+
+    #[test]
+    fn test_split_without_eof() {
+        let mut data = b"content".to_vec();
+        data.extend_from_slice(&create_record(0));
+        let (content, record) = split_sauce(&data);
+        assert_eq!(content, b"content");
+        assert_eq!(record, Some(&data[7..]));
+    }
+
+    #[test]
+    fn test_split_with_comments() {
+        let mut data = b"content".to_vec();
+        data.push(EOF_CHAR);
+        data.extend_from_slice(b"COMNT");
+        data.extend_from_slice(&[b' '; COMMENT_LEN * 2]);
+        data.extend_from_slice(&create_record(2));
+        assert_eq!(strip_sauce(&data), b"content");
+    }
+
+    #[test]
+    fn test_update_round_trip() {
+        let mut data = b"content".to_vec();
+        data.push(EOF_CHAR);
+        data.extend_from_slice(&create_record(0));
+
+        let updated = update_sauce(&data, Some(&create_edit(""))).unwrap();
+        assert_eq!(updated.len(), data.len());
+        let (content, record) = split_sauce(&updated);
+        assert_eq!(content, b"content");
+        let record = record.unwrap();
+        assert_eq!(&record[TITLE_OFFSET..TITLE_OFFSET + 5], b"Title");
+        assert_eq!(record[TITLE_OFFSET + 5], b' ');
+        assert_eq!(&record[AUTHOR_OFFSET..AUTHOR_OFFSET + 6], b"Author");
+        assert_eq!(&record[GROUP_OFFSET..GROUP_OFFSET + 5], b"Group");
+        assert_eq!(&record[DATE_OFFSET..DATE_OFFSET + 8], b"19950714");
+        assert_eq!(&record[FILE_SIZE_OFFSET..FILE_SIZE_OFFSET + 4], &7u32.to_le_bytes());
+        assert_eq!(record[COMMENTS_OFFSET], 0);
+        assert_eq!(record[FLAGS_OFFSET], ICE_FLAG | ASPECT_RATIO_LEGACY);
+        assert_eq!(&record[FONT_OFFSET..FONT_OFFSET + 8], b"IBM VGA\0");
+
+        // writing the same edit again doesn't change the file
+        assert_eq!(update_sauce(&updated, Some(&create_edit(""))).unwrap(), updated);
+    }
+
+    #[test]
+    fn test_update_comments() {
+        let mut data = b"content".to_vec();
+        data.extend_from_slice(&create_record(0));
+
+        let updated = update_sauce(&data, Some(&create_edit("first\nsecond\n"))).unwrap();
+        assert_eq!(updated.len(), 7 + 1 + 5 + 2 * COMMENT_LEN + RECORD_LEN);
+        assert_eq!(&updated[8..13], b"COMNT");
+        assert_eq!(&updated[13..18], b"first");
+        assert_eq!(&updated[13 + COMMENT_LEN..19 + COMMENT_LEN], b"second");
+        let (content, record) = split_sauce(&updated);
+        assert_eq!(content, b"content");
+        assert_eq!(record.unwrap()[COMMENTS_OFFSET], 2);
+
+        // removing the comments drops the comment block
+        let updated = update_sauce(&updated, Some(&create_edit(""))).unwrap();
+        assert_eq!(updated.len(), 7 + 1 + RECORD_LEN);
+        assert_eq!(strip_sauce(&updated), b"content");
+    }
+
+    #[test]
+    fn test_remove_sauce() {
+        let mut data = b"content".to_vec();
+        data.push(EOF_CHAR);
+        data.extend_from_slice(b"COMNT");
+        data.extend_from_slice(&[b' '; COMMENT_LEN]);
+        data.extend_from_slice(&create_record(1));
+        assert_eq!(update_sauce(&data, None).unwrap(), b"content");
+    }
+
+    #[test]
+    fn test_update_needs_record() {
+        assert!(update_sauce(b"content", Some(&create_edit(""))).is_err());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date(""), Ok(*b"        "));
+        assert_eq!(parse_date("1995-07-14"), Ok(*b"19950714"));
+        assert_eq!(parse_date("19950714"), Ok(*b"19950714"));
+        assert!(parse_date("1995-13-01").is_err());
+        assert!(parse_date("95-07-14").is_err());
+    }
+
+    #[test]
+    fn test_encode_cp437() {
+        assert_eq!(encode_cp437('A'), Some(b'A'));
+        assert_eq!(encode_cp437('Ç'), Some(0x80));
+        assert_eq!(encode_cp437('░'), Some(0xB0));
+        assert_eq!(encode_cp437('€'), None);
+    }
+}
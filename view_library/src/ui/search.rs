@@ -0,0 +1,234 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use walkdir::WalkDir;
+
+use super::{
+    archive::{is_archive, ArchiveSource},
+    file_filter::FileFilter,
+    file_view::{FileEntry, FileInfo},
+    loader, sauce_writer,
+};
+
+/// Archives inside archives are searched up to this depth.
+const MAX_ARCHIVE_DEPTH: usize = 2;
+/// Larger files are not searched for text content.
+const MAX_CONTENT_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Where a search result can be found, used to jump to the containing folder.
+#[derive(Clone, Debug)]
+pub struct SearchLocation {
+    /// Folder or archive on disk containing the result.
+    pub path: PathBuf,
+    /// Folder inside the archive at `path`, `None` for files on disk.
+    pub archive_folder: Option<PathBuf>,
+    /// Path of the result relative to the search root.
+    pub display_name: String,
+}
+
+pub struct SearchResult {
+    pub entry: FileEntry,
+    pub location: SearchLocation,
+}
+
+/// Walks a folder recursively on a worker thread and streams matching entries.
+pub struct Search {
+    pub root: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    scanned: Arc<AtomicUsize>,
+    result_rx: Receiver<SearchResult>,
+    handle: JoinHandle<()>,
+}
+
+impl Search {
+    pub fn start(root: PathBuf, query: &str, search_content: bool) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let scanned = Arc::new(AtomicUsize::new(0));
+        let (result_tx, result_rx) = channel();
+
+        let searcher = Searcher {
+            root: root.clone(),
+            filter: FileFilter::parse(query),
+            search_content,
+            cancelled: cancelled.clone(),
+            scanned: scanned.clone(),
+            result_tx,
+        };
+        let handle = thread::spawn(move || searcher.run());
+
+        Self {
+            root,
+            cancelled,
+            scanned,
+            result_rx,
+            handle,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn get_scanned(&self) -> usize {
+        self.scanned.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the results found since the last call.
+    pub fn poll(&self) -> Vec<SearchResult> {
+        self.result_rx.try_iter().collect()
+    }
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+struct Searcher {
+    root: PathBuf,
+    filter: FileFilter,
+    search_content: bool,
+    cancelled: Arc<AtomicBool>,
+    scanned: Arc<AtomicUsize>,
+    result_tx: Sender<SearchResult>,
+}
+
+impl Searcher {
+    fn run(&self) {
+        if self.root.is_file() {
+            self.search_archive(ArchiveSource::File(self.root.clone()), &self.root, None, 0);
+            return;
+        }
+
+        let walker = WalkDir::new(&self.root).min_depth(1).into_iter().filter_entry(|entry| {
+            // Do not search hidden files and folders.
+            !cfg!(unix) || !entry.file_name().to_string_lossy().starts_with('.')
+        });
+        for dir_entry in walker.filter_map(|result| result.ok()) {
+            if self.is_cancelled() {
+                return;
+            }
+            let path = dir_entry.into_path();
+            let entry = FileEntry {
+                file_info: FileInfo::new(path.clone()),
+                file_data: None,
+                archive_entry: None,
+                read_sauce: false,
+                sauce: None,
+            };
+            let is_dir = entry.is_dir();
+            if !is_dir && !path.is_file() {
+                continue;
+            }
+            let location = SearchLocation {
+                path: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+                archive_folder: None,
+                display_name: self.get_display_name(&path),
+            };
+            self.check_entry(entry, location);
+
+            if !is_dir && is_archive(&path) {
+                self.search_archive(ArchiveSource::File(path.clone()), &path, None, 0);
+            }
+        }
+    }
+
+    /// Searches all files of an archive, `outer` is the archive on disk and `outer_folder` the folder of nested archives in it.
+    fn search_archive(&self, source: ArchiveSource, outer: &Path, outer_folder: Option<&Path>, depth: usize) {
+        let entries = match source.list() {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::warn!("Error reading archive {}: {err}", source.name().display());
+                return;
+            }
+        };
+
+        for (path, dir, archive_entry) in entries {
+            if self.is_cancelled() {
+                return;
+            }
+            if dir {
+                continue;
+            }
+            let folder = outer_folder
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| path.parent().map(Path::to_path_buf).unwrap_or_default());
+            let location = SearchLocation {
+                path: outer.to_path_buf(),
+                archive_folder: Some(folder.clone()),
+                display_name: Path::new(&self.get_display_name(source.name())).join(&path).to_string_lossy().to_string(),
+            };
+            let entry = FileEntry {
+                file_info: FileInfo {
+                    path: path.clone(),
+                    dir: false,
+                    size: archive_entry.size,
+                    modified: None,
+                },
+                file_data: None,
+                archive_entry: Some(archive_entry.clone()),
+                read_sauce: false,
+                sauce: None,
+            };
+            self.check_entry(entry, location);
+
+            if depth < MAX_ARCHIVE_DEPTH && is_archive(&path) {
+                match archive_entry.read_data() {
+                    Ok(data) => {
+                        let name = source.name().join(&path);
+                        self.search_archive(ArchiveSource::Memory { name, data }, outer, Some(&folder), depth + 1);
+                    }
+                    Err(err) => log::warn!("Error reading archive {}: {err}", path.display()),
+                }
+            }
+        }
+    }
+
+    fn check_entry(&self, mut entry: FileEntry, location: SearchLocation) {
+        self.scanned.fetch_add(1, Ordering::Relaxed);
+        if entry.is_file() {
+            entry.load_sauce();
+        }
+
+        let matches = if self.search_content && self.has_searchable_content(&entry) {
+            match entry.get_data(|_, data| String::from_utf8_lossy(sauce_writer::strip_sauce(data)).to_lowercase()) {
+                Ok(content) => self.filter.matches_with_content(&entry, Some(&content)),
+                Err(_) => self.filter.matches(&entry),
+            }
+        } else {
+            self.filter.matches(&entry)
+        };
+
+        if matches {
+            // the receiver is gone when the search got dropped
+            let _ = self.result_tx.send(SearchResult { entry, location });
+        }
+    }
+
+    fn has_searchable_content(&self, entry: &FileEntry) -> bool {
+        if !entry.is_file() || entry.is_archive() || entry.file_info.size > MAX_CONTENT_SIZE {
+            return false;
+        }
+        !loader::is_image_extension(&loader::get_extension(&entry.file_info.path))
+    }
+
+    fn get_display_name(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root).unwrap_or(path).to_string_lossy().to_string()
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
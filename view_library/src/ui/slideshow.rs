@@ -0,0 +1,90 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use rand::seq::SliceRandom;
+use walkdir::WalkDir;
+
+use super::{file_view::FileView, loader, options::SlideshowOptions};
+
+/// Plays the files of a folder one after another.
+pub struct Slideshow {
+    playlist: Vec<PathBuf>,
+    position: usize,
+    shown_at: Instant,
+}
+
+impl Slideshow {
+    /// Creates the playlist from the shown files or, for recursive slideshows, all files below the current folder.
+    pub fn new(file_view: &FileView, options: &SlideshowOptions) -> Self {
+        let mut playlist: Vec<PathBuf> = if options.recursive && file_view.get_path().is_dir() {
+            WalkDir::new(file_view.get_path())
+                .sort_by_file_name()
+                .into_iter()
+                .filter_entry(|entry| !cfg!(unix) || !entry.file_name().to_string_lossy().starts_with('.'))
+                .filter_map(|result| result.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.into_path())
+                .filter(|path| loader::has_preview(&loader::get_extension(path)))
+                .collect()
+        } else {
+            // start with the selected file
+            let start = file_view.selected_file.unwrap_or_default();
+            file_view
+                .files
+                .iter()
+                .skip(start)
+                .chain(file_view.files.iter().take(start))
+                .filter(|entry| entry.is_file() && !entry.is_archive() && loader::has_preview(&loader::get_extension(&entry.file_info.path)))
+                .map(|entry| entry.file_info.path.clone())
+                .collect()
+        };
+        if options.shuffle {
+            playlist.shuffle(&mut rand::thread_rng());
+        }
+
+        Self {
+            playlist,
+            position: 0,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.playlist.is_empty()
+    }
+
+    pub fn get_current(&self) -> Option<&PathBuf> {
+        self.playlist.get(self.position)
+    }
+
+    /// Restarts the delay of the current piece, used while it's loading.
+    pub fn restart_timer(&mut self) {
+        self.shown_at = Instant::now();
+    }
+
+    /// True if the current piece was shown long enough, `busy` pieces are still scrolling.
+    pub fn should_advance(&self, options: &SlideshowOptions, busy: bool) -> bool {
+        if busy && options.wait_for_end {
+            return false;
+        }
+        self.shown_at.elapsed() >= Duration::from_secs(options.delay.max(1))
+    }
+
+    /// Moves to the next piece, returns `None` at the end of the playlist if it doesn't repeat.
+    pub fn next(&mut self, options: &SlideshowOptions) -> Option<&PathBuf> {
+        self.position += 1;
+        if self.position >= self.playlist.len() {
+            if !options.repeat {
+                return None;
+            }
+            self.position = 0;
+            if options.shuffle {
+                self.playlist.shuffle(&mut rand::thread_rng());
+            }
+        }
+        self.restart_timer();
+        self.get_current()
+    }
+}
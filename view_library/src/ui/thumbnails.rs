@@ -0,0 +1,215 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::UNIX_EPOCH,
+};
+
+use eframe::{
+    egui::{Context, TextureHandle, TextureOptions},
+    epaint::ColorImage,
+};
+use image::{imageops::FilterType, RgbaImage};
+
+use super::{archive::ArchiveSource, file_view::FileEntry, loader};
+
+pub const THUMBNAIL_WIDTH: u32 = 120;
+pub const THUMBNAIL_HEIGHT: u32 = 150;
+
+pub enum ThumbnailState {
+    Loading,
+    Loaded(TextureHandle),
+    Failed,
+}
+
+struct ThumbnailRequest {
+    generation: usize,
+    entry: FileEntry,
+}
+
+struct ThumbnailResult {
+    generation: usize,
+    path: PathBuf,
+    image: Option<ColorImage>,
+}
+
+/// Renders thumbnails on a worker thread and keeps them in a disk cache.
+pub struct Thumbnails {
+    thumbnails: HashMap<PathBuf, ThumbnailState>,
+    generation: Arc<AtomicUsize>,
+    request_tx: Sender<ThumbnailRequest>,
+    result_rx: Receiver<ThumbnailResult>,
+}
+
+impl Default for Thumbnails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Thumbnails {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = channel::<ThumbnailRequest>();
+        let (result_tx, result_rx) = channel();
+        let generation = Arc::new(AtomicUsize::new(0));
+
+        let thread_generation = generation.clone();
+        thread::spawn(move || {
+            while let Ok(request) = request_rx.recv() {
+                // skip requests of folders that are no longer shown
+                if request.generation != thread_generation.load(Ordering::Relaxed) {
+                    continue;
+                }
+                let image = get_thumbnail(&request.entry);
+                let result = ThumbnailResult {
+                    generation: request.generation,
+                    path: request.entry.file_info.path.clone(),
+                    image,
+                };
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            thumbnails: HashMap::new(),
+            generation,
+            request_tx,
+            result_rx,
+        }
+    }
+
+    /// Drops all thumbnails and pending requests, called when the folder changes.
+    pub fn clear(&mut self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.thumbnails.clear();
+    }
+
+    /// Renders the thumbnail of a changed file again the next time it's shown.
+    pub fn invalidate(&mut self, path: &Path) {
+        self.thumbnails.remove(path);
+    }
+
+    /// Moves finished thumbnails into textures.
+    pub fn update(&mut self, ctx: &Context) {
+        let generation = self.generation.load(Ordering::Relaxed);
+        while let Ok(result) = self.result_rx.try_recv() {
+            if result.generation != generation {
+                continue;
+            }
+            let state = match result.image {
+                Some(image) => ThumbnailState::Loaded(ctx.load_texture(result.path.to_string_lossy(), image, TextureOptions::LINEAR)),
+                None => ThumbnailState::Failed,
+            };
+            self.thumbnails.insert(result.path, state);
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.thumbnails.values().any(|state| matches!(state, ThumbnailState::Loading))
+    }
+
+    /// Returns the thumbnail of an entry, requests it if it hasn't been rendered yet.
+    pub fn get(&mut self, entry: &FileEntry) -> &ThumbnailState {
+        let path = entry.file_info.path.clone();
+        if !self.thumbnails.contains_key(&path) {
+            let request = ThumbnailRequest {
+                generation: self.generation.load(Ordering::Relaxed),
+                entry: entry.clone(),
+            };
+            let state = if self.request_tx.send(request).is_ok() {
+                ThumbnailState::Loading
+            } else {
+                ThumbnailState::Failed
+            };
+            self.thumbnails.insert(path.clone(), state);
+        }
+        &self.thumbnails[&path]
+    }
+}
+
+fn get_thumbnail(entry: &FileEntry) -> Option<ColorImage> {
+    let cache_file = get_cache_file(entry);
+    if let Some(cache_file) = &cache_file {
+        if cache_file.exists() {
+            match image::open(cache_file) {
+                Ok(img) => return Some(to_color_image(&img.to_rgba8())),
+                Err(err) => log::warn!("Error reading cached thumbnail {}: {err}", cache_file.display()),
+            }
+        }
+    }
+
+    let img = match entry.get_data(|path, data| loader::render_data(path, data)) {
+        Ok(Ok(img)) => img,
+        Ok(Err(err)) | Err(err) => {
+            log::warn!("Error rendering thumbnail for {}: {err}", entry.file_info.path.display());
+            return None;
+        }
+    };
+    let img = scale_thumbnail(&img);
+
+    if let Some(cache_file) = &cache_file {
+        if let Err(err) = img.save_with_format(cache_file, image::ImageFormat::Png) {
+            log::error!("Error writing thumbnail {}: {err}", cache_file.display());
+        }
+    }
+    Some(to_color_image(&img))
+}
+
+/// Scales to the thumbnail width, long pieces are cut off at the bottom.
+fn scale_thumbnail(img: &RgbaImage) -> RgbaImage {
+    let width = img.width().max(1);
+    let visible_height = (img.height() as u64).min(width as u64 * THUMBNAIL_HEIGHT as u64 / THUMBNAIL_WIDTH as u64) as u32;
+    let top = image::imageops::crop_imm(img, 0, 0, width, visible_height).to_image();
+    let height = ((visible_height as u64 * THUMBNAIL_WIDTH as u64) / width as u64).max(1) as u32;
+    image::imageops::resize(&top, THUMBNAIL_WIDTH, height, FilterType::Triangle)
+}
+
+fn to_color_image(img: &RgbaImage) -> ColorImage {
+    ColorImage::from_rgba_unmultiplied([img.width() as usize, img.height() as usize], img.as_raw())
+}
+
+/// Cache file name derived from path, modification time and size, `None` for entries that can't be cached.
+fn get_cache_file(entry: &FileEntry) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    entry.file_info.path.hash(&mut hasher);
+
+    let file_path = match &entry.archive_entry {
+        Some(archive_entry) => {
+            // nested archives have no stable location on disk
+            let ArchiveSource::File(archive_path) = &archive_entry.source else {
+                return None;
+            };
+            archive_entry.index.hash(&mut hasher);
+            archive_entry.size.hash(&mut hasher);
+            archive_path
+        }
+        None => {
+            if entry.file_data.is_some() {
+                return None;
+            }
+            &entry.file_info.path
+        }
+    };
+    file_path.hash(&mut hasher);
+    let metadata = fs::metadata(file_path).ok()?;
+    metadata.len().hash(&mut hasher);
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    modified.as_nanos().hash(&mut hasher);
+
+    let proj_dirs = directories::ProjectDirs::from("com", "GitHub", "icy_view")?;
+    let cache_dir = proj_dirs.cache_dir().join("thumbnails");
+    if !cache_dir.exists() && fs::create_dir_all(&cache_dir).is_err() {
+        log::error!("Can't create thumbnail cache directory {:?}", cache_dir);
+        return None;
+    }
+    Some(cache_dir.join(format!("{:016x}.png", hasher.finish())))
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver},
+    time::{Duration, Instant},
+};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Changes are reported once the folder has been quiet for this long, tools write files in several steps.
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Watches the shown folder and collects the paths that were added, removed, renamed or modified in it.
+pub struct FolderWatcher {
+    watcher: Option<RecommendedWatcher>,
+    event_rx: Receiver<notify::Result<Event>>,
+    watched_path: Option<PathBuf>,
+    changed_paths: HashSet<PathBuf>,
+    last_event: Instant,
+}
+
+impl Default for FolderWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FolderWatcher {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = channel();
+        let watcher = match notify::recommended_watcher(event_tx) {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                log::error!("Can't watch folders: {err}");
+                None
+            }
+        };
+        Self {
+            watcher,
+            event_rx,
+            watched_path: None,
+            changed_paths: HashSet::new(),
+            last_event: Instant::now(),
+        }
+    }
+
+    /// Watches `path` instead of the previous folder, `None` stops watching.
+    pub fn watch(&mut self, path: Option<&Path>) {
+        if self.watched_path.as_deref() == path {
+            return;
+        }
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+        if let Some(old_path) = self.watched_path.take() {
+            // fails if the folder has been removed in the meantime
+            let _ = watcher.unwatch(&old_path);
+        }
+        while self.event_rx.try_recv().is_ok() {}
+        self.changed_paths.clear();
+
+        if let Some(path) = path {
+            match watcher.watch(path, RecursiveMode::NonRecursive) {
+                Ok(()) => self.watched_path = Some(path.to_path_buf()),
+                Err(err) => log::error!("Can't watch {}: {err}", path.display()),
+            }
+        }
+    }
+
+    /// Returns the changed paths once no new events arrived for a while.
+    pub fn poll(&mut self) -> Option<Vec<PathBuf>> {
+        while let Ok(event) = self.event_rx.try_recv() {
+            match event {
+                Ok(event) => {
+                    if event.kind.is_access() {
+                        continue;
+                    }
+                    self.changed_paths.extend(event.paths);
+                    self.last_event = Instant::now();
+                }
+                Err(err) => log::error!("Error watching folder: {err}"),
+            }
+        }
+        if self.changed_paths.is_empty() || self.last_event.elapsed() < SETTLE_TIME {
+            return None;
+        }
+        Some(self.changed_paths.drain().collect())
+    }
+}